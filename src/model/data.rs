@@ -1,20 +1,70 @@
 ///! Implements data structures for https://www.w3.org/TR/rdf11-concepts/
 ///! Inspired by [RDFjs](http://rdf.js.org/)
+use model::datatypes::DatatypeRegistry;
+use model::iri::*;
+use model::vocab::rdf;
+use model::xsd::*;
+use std::any::Any;
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::hash_map::RandomState;
+use std::collections::HashSet;
+use std::error::Error;
 use std::fmt;
+use std::hash::BuildHasher;
+use std::hash::Hash;
+use std::hash::Hasher;
 use std::option::Option;
 use std::sync::Arc;
 use std::sync::Mutex;
 
 /// A RDF [IRI](https://www.w3.org/TR/rdf11-concepts/#dfn-iri)
-#[derive(Eq, PartialEq, Debug, Clone, Hash)]
+///
+/// Its IRI is shared between its clones.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Hash)]
 pub struct NamedNode {
-    iri: String,
+    iri: Arc<str>,
 }
 
 impl NamedNode {
     pub fn value(&self) -> &str {
         &self.iri
     }
+
+    /// Returns the IRI with its [percent-encoded](https://tools.ietf.org/html/rfc3986#section-2.1) octets decoded
+    ///
+    /// It is lossy and only intended for display: `http://x/a%2Fb` and `http://x/a/b` are different IRIs but are decoded the same way.
+    /// Invalid percent-encodings are kept as is and invalid UTF-8 sequences are replaced by U+FFFD.
+    pub fn decoded_value(&self) -> Cow<'_, str> {
+        let value = self.value();
+        if !value.contains('%') {
+            return Cow::Borrowed(value);
+        }
+        let bytes = value.as_bytes();
+        let mut decoded = Vec::with_capacity(bytes.len());
+        let mut i = 0;
+        while i < bytes.len() {
+            let escaped = if bytes[i] == b'%' {
+                value
+                    .get(i + 1..i + 3)
+                    .filter(|digits| digits.bytes().all(|c| c.is_ascii_hexdigit()))
+                    .and_then(|digits| u8::from_str_radix(digits, 16).ok())
+            } else {
+                None
+            };
+            match escaped {
+                Some(byte) => {
+                    decoded.push(byte);
+                    i += 3;
+                }
+                None => {
+                    decoded.push(bytes[i]);
+                    i += 1;
+                }
+            }
+        }
+        Cow::Owned(String::from_utf8_lossy(&decoded).into_owned())
+    }
 }
 
 impl fmt::Display for NamedNode {
@@ -24,9 +74,11 @@ impl fmt::Display for NamedNode {
 }
 
 /// A RDF [blank node](https://www.w3.org/TR/rdf11-concepts/#dfn-blank-node)
-#[derive(Eq, PartialEq, Debug, Clone, Hash)]
+///
+/// Its id is shared between its clones.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Hash)]
 pub struct BlankNode {
-    id: String,
+    id: Arc<str>,
 }
 
 impl BlankNode {
@@ -42,19 +94,27 @@ impl fmt::Display for BlankNode {
 }
 
 /// A RDF [literal](https://www.w3.org/TR/rdf11-concepts/#dfn-literal)
-#[derive(Eq, PartialEq, Debug, Clone, Hash)]
+///
+/// Its lexical form is shared between its clones.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Hash)]
 pub enum Literal {
-    SimpleLiteral(String),
-    LanguageTaggedString { value: String, language: String },
-    TypedLiteral { value: String, datatype: NamedNode },
+    SimpleLiteral(Arc<str>),
+    LanguageTaggedString {
+        value: Arc<str>,
+        language: String,
+    },
+    TypedLiteral {
+        value: Arc<str>,
+        datatype: NamedNode,
+    },
 }
 
 lazy_static! {
     static ref XSD_STRING: NamedNode = NamedNode {
-        iri: "http://www.w3.org/2001/XMLSchema#string".to_owned()
+        iri: "http://www.w3.org/2001/XMLSchema#string".into()
     };
     static ref RDF_LANG_STRING: NamedNode = NamedNode {
-        iri: "http://www.w3.org/1999/02/22-rdf-syntax-ns#langString".to_owned()
+        iri: "http://www.w3.org/1999/02/22-rdf-syntax-ns#langString".into()
     };
 }
 
@@ -93,6 +153,137 @@ impl Literal {
             _ => false,
         }
     }
+
+    /// Checks if the literal language tag matches a language range using [RFC 4647 basic filtering](https://tools.ietf.org/html/rfc4647#section-3.3.1)
+    ///
+    /// For example the `en` range matches `en` and `en-US` but not `fr` or `eng`. The `*` range matches all language tags.
+    /// Literals without language tag never match.
+    pub fn matches_language_range(&self, range: &str) -> bool {
+        let language = match self.language() {
+            Some(language) => language,
+            None => return false,
+        };
+        if range == "*" {
+            return true;
+        }
+        language
+            .get(..range.len())
+            .is_some_and(|prefix| prefix.eq_ignore_ascii_case(range))
+            && (language.len() == range.len() || language.as_bytes()[range.len()] == b'-')
+    }
+
+    /// Checks if the literal language tag matches a language range using [RFC 4647 extended filtering](https://tools.ietf.org/html/rfc4647#section-3.3.2)
+    ///
+    /// Unlike basic filtering, wildcards are allowed in any subtag and subtags may be skipped: `de-*-DE` and `de-DE` both match `de-Latn-DE`.
+    /// Literals without language tag never match.
+    pub fn matches_extended_language_range(&self, range: &str) -> bool {
+        let language = match self.language() {
+            Some(language) => language,
+            None => return false,
+        };
+        let mut range = range.split('-');
+        let mut tag = language.split('-').peekable();
+        match (range.next(), tag.next()) {
+            (Some(range), Some(tag)) if range == "*" || range.eq_ignore_ascii_case(tag) => (),
+            _ => return false,
+        }
+        for range_subtag in range {
+            if range_subtag == "*" {
+                continue;
+            }
+            loop {
+                match tag.next() {
+                    Some(tag_subtag) if tag_subtag.eq_ignore_ascii_case(range_subtag) => break,
+                    Some(tag_subtag) if tag_subtag.len() > 1 => (),
+                    _ => return false,
+                }
+            }
+        }
+        true
+    }
+
+    /// Decodes the bytes of a [xsd:hexBinary](https://www.w3.org/TR/xmlschema11-2/#hexBinary) or [xsd:base64Binary](https://www.w3.org/TR/xmlschema11-2/#base64Binary) literal
+    /// Returns None if the literal has an other datatype or an invalid lexical form
+    pub fn as_bytes(&self) -> Option<Vec<u8>> {
+        match self.datatype().value() {
+            XSD_HEX_BINARY => BinaryEncoding::Hex.decode(self.value()),
+            XSD_BASE64_BINARY => BinaryEncoding::Base64.decode(self.value()),
+            _ => None,
+        }
+    }
+
+    /// Returns the literal with the canonical lexical form of its value
+    ///
+    /// Only xsd:boolean, xsd:decimal, xsd:dateTime and the xsd:integer datatypes are canonicalized,
+    /// for example `"01.50"^^xsd:decimal` becomes `"1.5"^^xsd:decimal` and `"1"^^xsd:boolean` becomes `"true"^^xsd:boolean`.
+    /// The xsd:dateTime values keep their timezone offset, only its form and the fractional seconds are normalized:
+    /// `"2020-01-01T12:00:00.500+00:00"` becomes `"2020-01-01T12:00:00.5Z"`.
+    /// The other literals and the literals with an invalid lexical form are returned unchanged.
+    pub fn canonical(&self) -> Literal {
+        let datatype = self.datatype().value();
+        let canonical_value = if datatype == XSD_BOOLEAN {
+            canonicalize_boolean(self.value())
+        } else if datatype == XSD_DECIMAL {
+            canonicalize_decimal(self.value())
+        } else if XSD_INTEGER_TYPES.contains(&datatype) {
+            canonicalize_integer(self.value())
+        } else if datatype == XSD_DATE_TIME || datatype == XSD_DATE_TIME_STAMP {
+            canonicalize_date_time(self.value())
+        } else {
+            None
+        };
+        match (canonical_value, self) {
+            (Some(value), Literal::TypedLiteral { datatype, .. }) => Literal::TypedLiteral {
+                value: value.into(),
+                datatype: datatype.clone(),
+            },
+            _ => self.clone(),
+        }
+    }
+
+    /// Decodes the literal using the parser registered for its datatype in `registry`
+    ///
+    /// Returns None if there is no parser for the literal datatype, if the lexical form is invalid or if the parsed value is not a `T`.
+    pub fn as_custom<T: Any>(&self, registry: &DatatypeRegistry) -> Option<T> {
+        registry
+            .parse(self)?
+            .downcast()
+            .ok()
+            .map(|value: Box<T>| *value)
+    }
+
+    /// Returns the numeric type of the literal datatype or None if it is not a numeric datatype
+    ///
+    /// The lexical form is not validated.
+    pub fn numeric_datatype(&self) -> Option<NumericType> {
+        NumericType::from_datatype(self.datatype().value())
+    }
+
+    /// Parses the value of a numeric literal, `xsd:float` values being rounded to 32 bits
+    /// Returns None if the literal is not numeric or has an invalid lexical form
+    pub fn as_f64(&self) -> Option<f64> {
+        self.numeric_datatype()?.parse_f64(self.value())
+    }
+
+    /// Parses the value of a [xsd:duration](https://www.w3.org/TR/xmlschema11-2/#duration) literal, or of one of its subtypes
+    /// Returns None if the literal has an other datatype or an invalid lexical form
+    pub fn as_duration(&self) -> Option<XsdDuration> {
+        match self.datatype().value() {
+            XSD_DURATION | XSD_DAY_TIME_DURATION | XSD_YEAR_MONTH_DURATION => {
+                XsdDuration::parse(self.value())
+            }
+            _ => None,
+        }
+    }
+
+    /// Parses the value of a [xsd:dateTime](https://www.w3.org/TR/xmlschema11-2/#dateTime) or xsd:dateTimeStamp literal
+    /// Returns None if the literal has an other datatype or an invalid lexical form
+    pub fn as_date_time(&self) -> Option<XsdDateTime> {
+        match self.datatype().value() {
+            XSD_DATE_TIME | XSD_DATE_TIME_STAMP => XsdDateTime::parse(self.value()),
+            _ => None,
+        }
+    }
 }
 
 impl fmt::Display for Literal {
@@ -107,8 +298,146 @@ impl fmt::Display for Literal {
     }
 }
 
+/// A literal keeping both its original lexical form and its canonical form
+///
+/// It serializes as the original literal and compares values using the canonical literal (see `Literal::canonical`).
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Clone, Hash)]
+pub struct CanonicalLiteral {
+    original: Literal,
+    canonical: Literal,
+}
+
+impl CanonicalLiteral {
+    pub fn new(original: Literal) -> Self {
+        Self {
+            canonical: original.canonical(),
+            original,
+        }
+    }
+
+    /// The literal as it was built
+    pub fn original(&self) -> &Literal {
+        &self.original
+    }
+
+    /// The literal with the canonical lexical form
+    pub fn canonical(&self) -> &Literal {
+        &self.canonical
+    }
+
+    /// Checks if the two literals have the same value, like `"1.50"^^xsd:decimal` and `"1.5"^^xsd:decimal`
+    /// or the same instant `"2020-01-01T12:00:00Z"^^xsd:dateTime` and `"2020-01-01T14:00:00+02:00"^^xsd:dateTime`
+    pub fn value_eq(&self, other: &CanonicalLiteral) -> bool {
+        self.value_cmp(other) == Some(Ordering::Equal)
+    }
+
+    /// Compares the values of the two literals
+    ///
+    /// The literals with the same canonical form are equal and the xsd:dateTime literals are ordered by instant (see `XsdDateTime`).
+    /// Returns None for the other literals.
+    pub fn value_cmp(&self, other: &CanonicalLiteral) -> Option<Ordering> {
+        if self.canonical == other.canonical {
+            return Some(Ordering::Equal);
+        }
+        self.canonical
+            .as_date_time()?
+            .partial_cmp(&other.canonical.as_date_time()?)
+    }
+
+    pub fn into_original(self) -> Literal {
+        self.original
+    }
+}
+
+impl From<Literal> for CanonicalLiteral {
+    fn from(original: Literal) -> Self {
+        Self::new(original)
+    }
+}
+
+impl fmt::Display for CanonicalLiteral {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        self.original.fmt(f)
+    }
+}
+
+/// An error raised when trying to build an invalid RDF [literal](https://www.w3.org/TR/rdf11-concepts/#dfn-literal)
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum LiteralError {
+    /// A typed literal with the http://www.w3.org/1999/02/22-rdf-syntax-ns#langString datatype.
+    /// [Language-tagged strings](https://www.w3.org/TR/rdf11-concepts/#dfn-language-tagged-string) should be built with `DataFactory::language_tagged_literal`.
+    LangStringWithoutLanguage,
+    /// A language tag that does not match the Turtle [LANGTAG](https://www.w3.org/TR/turtle/#grammar-production-LANGTAG) grammar `[a-zA-Z]+ ('-' [a-zA-Z0-9]+)*`
+    InvalidLanguageTag(String),
+}
+
+impl fmt::Display for LiteralError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LiteralError::LangStringWithoutLanguage => write!(
+                f,
+                "{} literals should have a language tag",
+                *RDF_LANG_STRING
+            ),
+            LiteralError::InvalidLanguageTag(language) => {
+                write!(f, "{} is not a valid language tag", language)
+            }
+        }
+    }
+}
+
+impl Error for LiteralError {}
+
+/// An error raised by `DataFactory::triple_checked` when a term is not allowed at its position in a triple
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum TripleError {
+    /// The subject is a literal while it should be an IRI or a blank node
+    InvalidSubject(Term),
+    /// The predicate is a blank node or a literal while it should be an IRI
+    InvalidPredicate(Term),
+}
+
+impl fmt::Display for TripleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TripleError::InvalidSubject(term) => write!(
+                f,
+                "The {} {} can not be the subject of a triple, only an IRI or a blank node can",
+                term.kind(),
+                term
+            ),
+            TripleError::InvalidPredicate(term) => write!(
+                f,
+                "The {} {} can not be the predicate of a triple, only an IRI can",
+                term.kind(),
+                term
+            ),
+        }
+    }
+}
+
+impl Error for TripleError {}
+
+/// The kind of a term returned by `Term::kind` and `NamedOrBlankNode::kind`
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Clone, Copy, Hash)]
+pub enum TermKind {
+    NamedNode,
+    BlankNode,
+    Literal,
+}
+
+impl fmt::Display for TermKind {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TermKind::NamedNode => write!(f, "named node"),
+            TermKind::BlankNode => write!(f, "blank node"),
+            TermKind::Literal => write!(f, "literal"),
+        }
+    }
+}
+
 /// The union of [IRIs](https://www.w3.org/TR/rdf11-concepts/#dfn-iri) and [blank nodes](https://www.w3.org/TR/rdf11-concepts/#dfn-blank-node).
-#[derive(Eq, PartialEq, Debug, Clone, Hash)]
+#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Hash)]
 pub enum NamedOrBlankNode {
     NamedNode(NamedNode),
     BlankNode(BlankNode),
@@ -121,6 +450,12 @@ impl NamedOrBlankNode {
             NamedOrBlankNode::BlankNode(node) => node.value(),
         }
     }
+    pub fn kind(&self) -> TermKind {
+        match self {
+            NamedOrBlankNode::NamedNode(_) => TermKind::NamedNode,
+            NamedOrBlankNode::BlankNode(_) => TermKind::BlankNode,
+        }
+    }
 }
 
 impl fmt::Display for NamedOrBlankNode {
@@ -146,7 +481,11 @@ impl From<BlankNode> for NamedOrBlankNode {
 
 /// A RDF [term](https://www.w3.org/TR/rdf11-concepts/#dfn-rdf-term)
 /// It is the union of [IRIs](https://www.w3.org/TR/rdf11-concepts/#dfn-iri), [blank nodes](https://www.w3.org/TR/rdf11-concepts/#dfn-blank-node) and [literals](https://www.w3.org/TR/rdf11-concepts/#dfn-literal).
-#[derive(Eq, PartialEq, Debug, Clone, Hash)]
+///
+/// Terms are totally ordered: IRIs first, then blank nodes and then literals, each kind being ordered by its values.
+///
+/// The `Debug` output is the N-Triples form of the term, the alternate `{:#?}` output shows its fields.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Hash)]
 pub enum Term {
     NamedNode(NamedNode),
     BlankNode(BlankNode),
@@ -161,8 +500,34 @@ impl Term {
             Term::Literal(literal) => literal.value(),
         }
     }
+
+    pub fn kind(&self) -> TermKind {
+        match self {
+            Term::NamedNode(_) => TermKind::NamedNode,
+            Term::BlankNode(_) => TermKind::BlankNode,
+            Term::Literal(_) => TermKind::Literal,
+        }
+    }
+
+    /// Returns the term as a [predicate](https://www.w3.org/TR/rdf11-concepts/#dfn-predicate) if it is an IRI
+    pub fn as_predicate(&self) -> Option<&NamedNode> {
+        match self {
+            Term::NamedNode(node) => Some(node),
+            _ => None,
+        }
+    }
+
+    /// Converts the term into a [predicate](https://www.w3.org/TR/rdf11-concepts/#dfn-predicate) or gives it back if it is not an IRI
+    pub fn into_predicate(self) -> Result<NamedNode, Term> {
+        match self {
+            Term::NamedNode(node) => Ok(node),
+            term => Err(term),
+        }
+    }
 }
 
+// The `Debug` implementations of the terms, triples and quads are in `rio::ntriples` to share its escaping
+
 impl fmt::Display for Term {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         match self {
@@ -222,7 +587,9 @@ pub trait TripleLike {
 }
 
 /// A [RDF triple](https://www.w3.org/TR/rdf11-concepts/#dfn-rdf-triple)
-#[derive(Eq, PartialEq, Debug, Clone, Hash)]
+///
+/// The `Debug` output is the N-Triples form of the triple, the alternate `{:#?}` output shows its fields.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Hash)]
 pub struct Triple {
     subject: NamedOrBlankNode,
     predicate: NamedNode,
@@ -235,6 +602,16 @@ impl fmt::Display for Triple {
     }
 }
 
+impl<S: Into<NamedOrBlankNode>, P: Into<NamedNode>, O: Into<Term>> From<(S, P, O)> for Triple {
+    fn from((subject, predicate, object): (S, P, O)) -> Self {
+        Triple {
+            subject: subject.into(),
+            predicate: predicate.into(),
+            object: object.into(),
+        }
+    }
+}
+
 impl TripleLike for Triple {
     fn subject(&self) -> &NamedOrBlankNode {
         return &self.subject;
@@ -261,6 +638,92 @@ impl TripleLike for Triple {
     }
 }
 
+/// A [RDF triple](https://www.w3.org/TR/rdf11-concepts/#dfn-rdf-triple) borrowing its terms
+///
+/// It allows to read triples stored elsewhere without cloning their terms.
+/// The `TripleLike` owned getters clone the borrowed terms.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Debug, Clone, Copy, Hash)]
+pub struct TripleRef<'a> {
+    subject: &'a NamedOrBlankNode,
+    predicate: &'a NamedNode,
+    object: &'a Term,
+}
+
+impl<'a> TripleRef<'a> {
+    pub fn new(subject: &'a NamedOrBlankNode, predicate: &'a NamedNode, object: &'a Term) -> Self {
+        TripleRef {
+            subject,
+            predicate,
+            object,
+        }
+    }
+
+    /// Clones the borrowed terms into an owned triple
+    pub fn into_owned(self) -> Triple {
+        Triple {
+            subject: self.subject.clone(),
+            predicate: self.predicate.clone(),
+            object: self.object.clone(),
+        }
+    }
+}
+
+impl<'a> fmt::Display for TripleRef<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} {} {} .", self.subject, self.predicate, self.object)
+    }
+}
+
+impl<'a> From<&'a Triple> for TripleRef<'a> {
+    fn from(triple: &'a Triple) -> Self {
+        TripleRef {
+            subject: &triple.subject,
+            predicate: &triple.predicate,
+            object: &triple.object,
+        }
+    }
+}
+
+impl<'a> From<TripleRef<'a>> for Triple {
+    fn from(triple: TripleRef<'a>) -> Self {
+        triple.into_owned()
+    }
+}
+
+impl<'a> PartialEq<Triple> for TripleRef<'a> {
+    fn eq(&self, other: &Triple) -> bool {
+        *self.subject == other.subject
+            && *self.predicate == other.predicate
+            && *self.object == other.object
+    }
+}
+
+impl<'a> TripleLike for TripleRef<'a> {
+    fn subject(&self) -> &NamedOrBlankNode {
+        self.subject
+    }
+
+    fn subject_owned(self) -> NamedOrBlankNode {
+        self.subject.clone()
+    }
+
+    fn predicate(&self) -> &NamedNode {
+        self.predicate
+    }
+
+    fn predicate_owned(self) -> NamedNode {
+        self.predicate.clone()
+    }
+
+    fn object(&self) -> &Term {
+        self.object
+    }
+
+    fn object_owned(self) -> Term {
+        self.object.clone()
+    }
+}
+
 /// The interface of [triples](https://www.w3.org/TR/rdf11-concepts/#dfn-rdf-triple) that are in a [RDF dataset](https://www.w3.org/TR/rdf11-concepts/#dfn-rdf-dataset)
 pub trait QuadLike: TripleLike {
     /// The name of the RDF [graph](https://www.w3.org/TR/rdf11-concepts/#dfn-rdf-graph) in which the triple is or None if it is in the [default graph](https://www.w3.org/TR/rdf11-concepts/#dfn-default-graph)
@@ -271,7 +734,9 @@ pub trait QuadLike: TripleLike {
 }
 
 /// A [triple](https://www.w3.org/TR/rdf11-concepts/#dfn-rdf-triple) in a [RDF dataset](https://www.w3.org/TR/rdf11-concepts/#dfn-rdf-dataset)
-#[derive(Eq, PartialEq, Debug, Clone, Hash)]
+///
+/// The `Debug` output is the N-Quads form of the quad, the alternate `{:#?}` output shows its fields.
+#[derive(Eq, PartialEq, Ord, PartialOrd, Clone, Hash)]
 pub struct Quad {
     subject: NamedOrBlankNode,
     predicate: NamedNode,
@@ -328,6 +793,132 @@ impl QuadLike for Quad {
     }
 }
 
+impl Triple {
+    /// Builds the [quad](https://www.w3.org/TR/rdf11-concepts/#dfn-rdf-dataset) of this triple in the given graph
+    ///
+    /// The graph name is only cloned once the quad is built.
+    pub fn in_graph(self, graph_name: &Option<NamedOrBlankNode>) -> Quad {
+        Quad {
+            subject: self.subject,
+            predicate: self.predicate,
+            object: self.object,
+            graph_name: graph_name.clone(),
+        }
+    }
+
+    /// Returns the term at the given position: 0 for the subject, 1 for the predicate and 2 for the object
+    pub fn term_at(&self, position: usize) -> Option<Term> {
+        match position {
+            0 => Some(self.subject.clone().into()),
+            1 => Some(self.predicate.clone().into()),
+            2 => Some(self.object.clone()),
+            _ => None,
+        }
+    }
+
+    /// Returns a 128 bits hash of the triple that does not depend on the process or the platform
+    ///
+    /// It is the [FNV-1a](http://www.isthe.com/chongo/tech/comp/fnv/) hash of an unambiguous encoding of the triple terms, so it could be stored as a key in external systems.
+    /// It is not a cryptographic hash and should not be relied on if the triples could be chosen to create collisions.
+    pub fn fingerprint(&self) -> u128 {
+        let mut hasher = Fnv128::default();
+        match &self.subject {
+            NamedOrBlankNode::NamedNode(node) => hasher.write_field(b'I', node.value()),
+            NamedOrBlankNode::BlankNode(node) => hasher.write_field(b'B', node.value()),
+        }
+        hasher.write_field(b'I', self.predicate.value());
+        match &self.object {
+            Term::NamedNode(node) => hasher.write_field(b'I', node.value()),
+            Term::BlankNode(node) => hasher.write_field(b'B', node.value()),
+            Term::Literal(Literal::SimpleLiteral(value)) => hasher.write_field(b'S', value),
+            Term::Literal(Literal::LanguageTaggedString { value, language }) => {
+                hasher.write_field(b'L', value);
+                hasher.write_field(b'@', language);
+            }
+            Term::Literal(Literal::TypedLiteral { value, datatype }) => {
+                hasher.write_field(b'T', value);
+                hasher.write_field(b'^', datatype.value());
+            }
+        }
+        hasher.0
+    }
+}
+
+/// The 128 bits [FNV-1a](http://www.isthe.com/chongo/tech/comp/fnv/) hash function
+struct Fnv128(u128);
+
+impl Default for Fnv128 {
+    fn default() -> Self {
+        Fnv128(0x6c62_272e_07bb_0142_62b8_2175_6295_c58d)
+    }
+}
+
+impl Fnv128 {
+    fn write(&mut self, bytes: &[u8]) {
+        for byte in bytes {
+            self.0 ^= u128::from(*byte);
+            self.0 = self
+                .0
+                .wrapping_mul(0x0000_0000_0100_0000_0000_0000_0000_013b);
+        }
+    }
+
+    /// Writes a tag, the value length and the value so that two different sequences of fields never have the same encoding
+    fn write_field(&mut self, tag: u8, value: &str) {
+        self.write(&[tag]);
+        self.write(&(value.len() as u64).to_le_bytes());
+        self.write(value.as_bytes());
+    }
+}
+
+impl Quad {
+    /// Returns the term at the given position: 0 for the subject, 1 for the predicate, 2 for the object and 3 for the graph name
+    ///
+    /// Returns None at position 3 if the quad is in the default graph.
+    pub fn term_at(&self, position: usize) -> Option<Term> {
+        match position {
+            0 => Some(self.subject.clone().into()),
+            1 => Some(self.predicate.clone().into()),
+            2 => Some(self.object.clone()),
+            3 => self.graph_name.clone().map(Term::from),
+            _ => None,
+        }
+    }
+}
+
+/// An extension of triple iterators to put all of their triples into the same graph
+pub trait IntoQuads: Iterator<Item = Triple> + Sized {
+    /// Converts the triples into quads in the graph `graph_name` (`None` for the [default graph](https://www.w3.org/TR/rdf11-concepts/#dfn-default-graph))
+    fn into_quads(self, graph_name: impl Into<Option<NamedOrBlankNode>>) -> QuadsIterator<Self> {
+        QuadsIterator {
+            triples: self,
+            graph_name: graph_name.into(),
+        }
+    }
+}
+
+impl<I: Iterator<Item = Triple>> IntoQuads for I {}
+
+/// An iterator putting triples into a graph. See `IntoQuads::into_quads`
+pub struct QuadsIterator<I: Iterator<Item = Triple>> {
+    triples: I,
+    graph_name: Option<NamedOrBlankNode>,
+}
+
+impl<I: Iterator<Item = Triple>> Iterator for QuadsIterator<I> {
+    type Item = Quad;
+
+    fn next(&mut self) -> Option<Quad> {
+        self.triples
+            .next()
+            .map(|triple| triple.in_graph(&self.graph_name))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.triples.size_hint()
+    }
+}
+
 /// An utility structure to generate bank node ids in a thread safe way
 #[derive(Debug, Clone)]
 struct U64IDProvider {
@@ -335,9 +926,14 @@ struct U64IDProvider {
 }
 
 impl U64IDProvider {
+    /// Returns a new id
+    ///
+    /// Panics if all the 2^64 - 1 ids have already been returned instead of wrapping around and returning duplicated ids.
     pub fn next(&self) -> u64 {
         let mut id = self.counter.lock().unwrap();
-        *id += 1;
+        *id = id
+            .checked_add(1)
+            .expect("No more blank node ids are available: the u64 counter overflowed");
         *id
     }
 }
@@ -350,39 +946,154 @@ impl Default for U64IDProvider {
     }
 }
 
+/// A set of strings shared in a thread safe way, used to give the same storage to equal strings
+#[derive(Debug, Clone, Default)]
+struct StringInterner {
+    strings: Arc<Mutex<HashSet<Arc<str>>>>,
+}
+
+impl StringInterner {
+    /// Returns the stored string equal to the given one, storing it if there is none
+    fn intern(&self, value: Arc<str>) -> Arc<str> {
+        let mut strings = self.strings.lock().unwrap();
+        match strings.get(&value) {
+            Some(interned) => interned.clone(),
+            None => {
+                strings.insert(value.clone());
+                value
+            }
+        }
+    }
+}
+
+/// The way the ids of new blank nodes are generated
+#[derive(Debug, Clone)]
+enum BlankNodeIdScheme {
+    /// A fixed prefix followed by a process-local counter value like `n1`, `n2`...
+    Counter {
+        prefix: String,
+        provider: U64IDProvider,
+    },
+    /// A random [UUID](https://tools.ietf.org/html/rfc4122) that is unique across processes
+    Uuid,
+}
+
+impl BlankNodeIdScheme {
+    fn next(&self) -> String {
+        match self {
+            BlankNodeIdScheme::Counter { prefix, provider } => {
+                format!("{}{}", prefix, provider.next())
+            }
+            BlankNodeIdScheme::Uuid => new_uuid(),
+        }
+    }
+}
+
+/// Builds a random version 4 UUID using the randomly seeded hashers of the standard library
+fn new_uuid() -> String {
+    let high = (RandomState::new().build_hasher().finish() & 0xffff_ffff_ffff_0fff) | 0x4000;
+    let low = (RandomState::new().build_hasher().finish() & 0x3fff_ffff_ffff_ffff)
+        | 0x8000_0000_0000_0000;
+    format!(
+        "{:08x}-{:04x}-{:04x}-{:04x}-{:012x}",
+        high >> 32,
+        (high >> 16) & 0xffff,
+        high & 0xffff,
+        low >> 48,
+        low & 0xffff_ffff_ffff
+    )
+}
+
 /// A structure creating RDF elements
 #[derive(Debug, Clone)]
 pub struct DataFactory {
-    blank_node_id_provider: U64IDProvider,
+    blank_node_id_scheme: BlankNodeIdScheme,
+    predicate_interner: Option<StringInterner>,
+    literal_interner: Option<StringInterner>,
 }
 
 impl Default for DataFactory {
     fn default() -> Self {
-        DataFactory {
-            blank_node_id_provider: U64IDProvider::default(),
-        }
+        DataFactory::with_blank_node_prefix("")
     }
 }
 
 impl DataFactory {
+    /// Builds a factory whose new blank nodes ids are the given prefix followed by a counter value
+    ///
+    /// For example `DataFactory::with_blank_node_prefix("n")` generates the ids `n1`, `n2`...
+    pub fn with_blank_node_prefix(prefix: impl Into<String>) -> Self {
+        DataFactory {
+            blank_node_id_scheme: BlankNodeIdScheme::Counter {
+                prefix: prefix.into(),
+                provider: U64IDProvider::default(),
+            },
+            predicate_interner: None,
+            literal_interner: None,
+        }
+    }
+
+    /// Builds a factory whose new blank nodes ids are random UUIDs
+    ///
+    /// These ids are unique across processes, which is useful when merging data generated by multiple processes.
+    pub fn with_uuid_blank_nodes() -> Self {
+        DataFactory {
+            blank_node_id_scheme: BlankNodeIdScheme::Uuid,
+            predicate_interner: None,
+            literal_interner: None,
+        }
+    }
+
+    /// Makes `triple` and `quad` share the IRI of the predicates equal to a predicate already given to the factory
+    ///
+    /// Documents usually have few distinct predicates, so the parsed triples only keep one copy of each predicate IRI.
+    /// The predicate IRIs are kept as long as the factory or one of its clones is alive.
+    pub fn with_predicate_interning(mut self) -> Self {
+        self.predicate_interner = Some(StringInterner::default());
+        self
+    }
+
+    /// Makes the literals built by the factory share the lexical form of the literals already built with an equal lexical form
+    ///
+    /// It saves memory on the graphs with many repeated literal values.
+    /// The lexical forms are kept as long as the factory or one of its clones is alive.
+    pub fn with_literal_interning(mut self) -> Self {
+        self.literal_interner = Some(StringInterner::default());
+        self
+    }
+
     /// Builds a RDF [IRI](https://www.w3.org/TR/rdf11-concepts/#dfn-iri)
     pub fn named_node(&self, iri: impl Into<String>) -> NamedNode {
-        NamedNode { iri: iri.into() }
+        NamedNode {
+            iri: iri.into().into(),
+        }
+    }
+
+    /// Builds a RDF [IRI](https://www.w3.org/TR/rdf11-concepts/#dfn-iri) by resolving a relative reference against a base IRI following [RFC 3986](https://tools.ietf.org/html/rfc3986#section-5.2)
+    /// Returns an error if the base or the resolved IRI is not a valid absolute IRI
+    pub fn resolve_named_node(
+        &self,
+        reference: &str,
+        base: &NamedNode,
+    ) -> Result<NamedNode, IriError> {
+        resolve_iri(reference, base.value()).map(|iri| self.named_node(iri))
     }
 
     /// Builds a RDF [blank node](https://www.w3.org/TR/rdf11-concepts/#dfn-blank-node) with a known id
     pub fn blank_node(&self, id: impl Into<String>) -> BlankNode {
-        BlankNode { id: id.into() }
+        BlankNode {
+            id: id.into().into(),
+        }
     }
 
     /// Builds a new RDF [blank node](https://www.w3.org/TR/rdf11-concepts/#dfn-blank-node) with a unique id
     pub fn new_blank_node(&self) -> BlankNode {
-        self.blank_node(self.blank_node_id_provider.next().to_string())
+        self.blank_node(self.blank_node_id_scheme.next())
     }
 
     /// Builds a RDF [simple literal](https://www.w3.org/TR/rdf11-concepts/#dfn-simple-literal)
     pub fn simple_literal(&self, value: impl Into<String>) -> Literal {
-        Literal::SimpleLiteral(value.into())
+        Literal::SimpleLiteral(self.literal_value(value.into()))
     }
 
     /// Builds a RDF [literal](https://www.w3.org/TR/rdf11-concepts/#dfn-literal) with a [datatype](https://www.w3.org/TR/rdf11-concepts/#dfn-datatype-iri)
@@ -393,11 +1104,31 @@ impl DataFactory {
     ) -> Literal {
         //TODO: find the best representation
         Literal::TypedLiteral {
-            value: value.into(),
+            value: self.literal_value(value.into()),
             datatype: datatype.into(),
         }
     }
 
+    /// Builds a RDF [literal](https://www.w3.org/TR/rdf11-concepts/#dfn-literal) with a [datatype](https://www.w3.org/TR/rdf11-concepts/#dfn-datatype-iri)
+    /// and returns an error if the datatype is http://www.w3.org/1999/02/22-rdf-syntax-ns#langString
+    pub fn typed_literal_checked(
+        &self,
+        value: impl Into<String>,
+        datatype: impl Into<NamedNode>,
+    ) -> Result<Literal, LiteralError> {
+        let datatype = datatype.into();
+        if datatype == *RDF_LANG_STRING {
+            Err(LiteralError::LangStringWithoutLanguage)
+        } else {
+            Ok(self.typed_literal(value, datatype))
+        }
+    }
+
+    /// Builds a [xsd:hexBinary](https://www.w3.org/TR/xmlschema11-2/#hexBinary) or [xsd:base64Binary](https://www.w3.org/TR/xmlschema11-2/#base64Binary) literal encoding the bytes
+    pub fn binary_literal(&self, bytes: &[u8], encoding: BinaryEncoding) -> Literal {
+        self.typed_literal(encoding.encode(bytes), self.named_node(encoding.datatype()))
+    }
+
     /// Builds a RDF [language-tagged string](https://www.w3.org/TR/rdf11-concepts/#dfn-language-tagged-string)
     pub fn language_tagged_literal(
         &self,
@@ -405,11 +1136,31 @@ impl DataFactory {
         language: impl Into<String>,
     ) -> Literal {
         Literal::LanguageTaggedString {
-            value: value.into(),
+            value: self.literal_value(value.into()),
             language: language.into(),
         }
     }
 
+    /// Builds a RDF [language-tagged string](https://www.w3.org/TR/rdf11-concepts/#dfn-language-tagged-string)
+    /// and returns an error if the language tag does not match `[a-zA-Z]+ ('-' [a-zA-Z0-9]+)*`
+    pub fn language_tagged_literal_checked(
+        &self,
+        value: impl Into<String>,
+        language: impl Into<String>,
+    ) -> Result<Literal, LiteralError> {
+        let language = language.into();
+        let mut subtags = language.split('-');
+        let is_valid = subtags
+            .next()
+            .is_some_and(|tag| !tag.is_empty() && tag.chars().all(|c| c.is_ascii_alphabetic()))
+            && subtags.all(|tag| !tag.is_empty() && tag.chars().all(|c| c.is_ascii_alphanumeric()));
+        if is_valid {
+            Ok(self.language_tagged_literal(value, language))
+        } else {
+            Err(LiteralError::InvalidLanguageTag(language))
+        }
+    }
+
     /// Builds a RDF [triple](https://www.w3.org/TR/rdf11-concepts/#dfn-rdf-triple)
     pub fn triple(
         &self,
@@ -419,11 +1170,64 @@ impl DataFactory {
     ) -> Triple {
         Triple {
             subject: subject.into(),
-            predicate: predicate.into(),
+            predicate: self.intern_predicate(predicate.into()),
             object: object.into(),
         }
     }
 
+    /// Builds a RDF [triple](https://www.w3.org/TR/rdf11-concepts/#dfn-rdf-triple) from terms of any kind,
+    /// returning an error if the subject is a literal or the predicate is not an IRI
+    ///
+    /// It is useful to build triples from untyped inputs like generic tuples.
+    pub fn triple_checked(
+        &self,
+        subject: Term,
+        predicate: Term,
+        object: Term,
+    ) -> Result<Triple, TripleError> {
+        let subject = match subject {
+            Term::NamedNode(node) => NamedOrBlankNode::from(node),
+            Term::BlankNode(node) => NamedOrBlankNode::from(node),
+            Term::Literal(_) => return Err(TripleError::InvalidSubject(subject)),
+        };
+        let predicate = match predicate {
+            Term::NamedNode(node) => node,
+            _ => return Err(TripleError::InvalidPredicate(predicate)),
+        };
+        Ok(self.triple(subject, predicate, object))
+    }
+
+    /// Describes a triple using the [RDF reification vocabulary](https://www.w3.org/TR/rdf11-mt/#reification)
+    ///
+    /// Returns a new statement blank node and the `rdf:type rdf:Statement`, `rdf:subject`, `rdf:predicate` and `rdf:object` triples describing it.
+    /// The reified triple itself is not asserted. `Graph::reified_statements` does the opposite conversion.
+    pub fn reify(&self, triple: &Triple) -> (BlankNode, Vec<Triple>) {
+        let statement = self.new_blank_node();
+        let triples = vec![
+            self.triple(
+                statement.clone(),
+                self.named_node(rdf::TYPE),
+                self.named_node(rdf::STATEMENT),
+            ),
+            self.triple(
+                statement.clone(),
+                self.named_node(rdf::SUBJECT),
+                triple.subject.clone(),
+            ),
+            self.triple(
+                statement.clone(),
+                self.named_node(rdf::PREDICATE),
+                triple.predicate.clone(),
+            ),
+            self.triple(
+                statement.clone(),
+                self.named_node(rdf::OBJECT),
+                triple.object.clone(),
+            ),
+        ];
+        (statement, triples)
+    }
+
     /// Builds a RDF [triple](https://www.w3.org/TR/rdf11-concepts/#dfn-rdf-triple) in a [RDF dataset](https://www.w3.org/TR/rdf11-concepts/#dfn-rdf-dataset)
     pub fn quad(
         &self,
@@ -434,9 +1238,47 @@ impl DataFactory {
     ) -> Quad {
         Quad {
             subject: subject.into(),
-            predicate: predicate.into(),
+            predicate: self.intern_predicate(predicate.into()),
             object: object.into(),
             graph_name: graph_name.into(),
         }
     }
+
+    fn literal_value(&self, value: String) -> Arc<str> {
+        match &self.literal_interner {
+            Some(interner) => interner.intern(value.into()),
+            None => value.into(),
+        }
+    }
+
+    fn intern_predicate(&self, predicate: NamedNode) -> NamedNode {
+        match &self.predicate_interner {
+            Some(interner) => NamedNode {
+                iri: interner.intern(predicate.iri),
+            },
+            None => predicate,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_id_provider_last_id() {
+        let provider = U64IDProvider {
+            counter: Arc::new(Mutex::new(u64::MAX - 1)),
+        };
+        assert_eq!(provider.next(), u64::MAX);
+    }
+
+    #[test]
+    #[should_panic(expected = "No more blank node ids are available")]
+    fn test_id_provider_overflow() {
+        let provider = U64IDProvider {
+            counter: Arc::new(Mutex::new(u64::MAX)),
+        };
+        provider.next();
+    }
 }
@@ -0,0 +1,170 @@
+//! Implements in-memory [RDF datasets](https://www.w3.org/TR/rdf11-concepts/#dfn-rdf-dataset)
+use model::data::*;
+use model::graph::skolem_iri_prefix;
+use model::graph::Graph;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::iter::FromIterator;
+
+/// An in-memory [RDF dataset](https://www.w3.org/TR/rdf11-concepts/#dfn-rdf-dataset)
+#[derive(Debug, Clone, Default)]
+pub struct Dataset {
+    quads: HashSet<Quad>,
+}
+
+impl Dataset {
+    /// Builds an empty dataset able to store `capacity` quads without reallocating
+    pub fn with_capacity(capacity: usize) -> Self {
+        Dataset {
+            quads: HashSet::with_capacity(capacity),
+        }
+    }
+
+    /// Adds a quad to the dataset and returns false if it was already in it
+    pub fn insert(&mut self, quad: Quad) -> bool {
+        self.quads.insert(quad)
+    }
+
+    /// Removes a quad from the dataset and returns false if it was not in it
+    pub fn remove(&mut self, quad: &Quad) -> bool {
+        self.quads.remove(quad)
+    }
+
+    pub fn contains(&self, quad: &Quad) -> bool {
+        self.quads.contains(quad)
+    }
+
+    /// The number of quads in the dataset
+    pub fn len(&self) -> usize {
+        self.quads.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.quads.is_empty()
+    }
+
+    /// Iterates on the dataset quads in an arbitrary order
+    pub fn iter(&self) -> impl Iterator<Item = &Quad> {
+        self.quads.iter()
+    }
+
+    /// Builds a graph with the triples of all the dataset graphs, ignoring the graph names
+    ///
+    /// Triples present in several graphs are only added once and blank nodes keep their identifiers.
+    pub fn to_merged_graph(&self) -> Graph {
+        self.quads
+            .iter()
+            .map(|quad| {
+                Triple::from((
+                    quad.subject().clone(),
+                    quad.predicate().clone(),
+                    quad.object().clone(),
+                ))
+            })
+            .collect()
+    }
+
+    /// Returns the distinct blank nodes used as graph names, sorted
+    ///
+    /// Some stores only support IRI graph names: these graphs should be rejected or renamed with `skolemize_graph_names` before loading the dataset into them.
+    pub fn find_blank_node_graphs(&self) -> Vec<&BlankNode> {
+        let graphs: HashSet<&BlankNode> = self
+            .quads
+            .iter()
+            .filter_map(|quad| match quad.graph_name() {
+                Some(NamedOrBlankNode::BlankNode(node)) => Some(node),
+                _ => None,
+            })
+            .collect();
+        let mut graphs: Vec<&BlankNode> = graphs.into_iter().collect();
+        graphs.sort();
+        graphs
+    }
+
+    /// Replaces the blank node graph names by [skolem IRIs](https://www.w3.org/TR/rdf11-concepts/#section-skolemization) like `{base}/.well-known/genid/{id}`
+    ///
+    /// The IRIs are the same as the ones built by `Graph::skolemize`. The blank nodes in the other positions are kept.
+    pub fn skolemize_graph_names(&mut self, base: &str) {
+        let data_factory = DataFactory::default();
+        let prefix = skolem_iri_prefix(base);
+        self.quads = self
+            .quads
+            .drain()
+            .map(|quad| match quad.graph_name() {
+                Some(NamedOrBlankNode::BlankNode(node)) => {
+                    let graph_name = data_factory.named_node(format!("{}{}", prefix, node.value()));
+                    data_factory.quad(
+                        quad.subject().clone(),
+                        quad.predicate().clone(),
+                        quad.object().clone(),
+                        Some(graph_name.into()),
+                    )
+                }
+                _ => quad,
+            })
+            .collect();
+    }
+
+    /// Compares the dataset with an other version of it, graph by graph
+    ///
+    /// The quads of `other` that are not in this dataset are reported as added and the quads of this dataset that are not in `other` as removed.
+    pub fn diff(&self, other: &Dataset) -> DatasetDiff {
+        let mut graphs: HashMap<Option<NamedOrBlankNode>, GraphDiff> = HashMap::default();
+        for quad in other.quads.difference(&self.quads) {
+            graphs
+                .entry(quad.graph_name().clone())
+                .or_default()
+                .added
+                .push(quad.clone());
+        }
+        for quad in self.quads.difference(&other.quads) {
+            graphs
+                .entry(quad.graph_name().clone())
+                .or_default()
+                .removed
+                .push(quad.clone());
+        }
+        for diff in graphs.values_mut() {
+            diff.added.sort();
+            diff.removed.sort();
+        }
+        DatasetDiff { graphs }
+    }
+}
+
+impl FromIterator<Quad> for Dataset {
+    fn from_iter<I: IntoIterator<Item = Quad>>(iter: I) -> Self {
+        Dataset {
+            quads: HashSet::from_iter(iter),
+        }
+    }
+}
+
+impl Extend<Quad> for Dataset {
+    fn extend<I: IntoIterator<Item = Quad>>(&mut self, iter: I) {
+        self.quads.extend(iter)
+    }
+}
+
+/// The differences between two datasets returned by `Dataset::diff`
+#[derive(Eq, PartialEq, Debug, Clone, Default)]
+pub struct DatasetDiff {
+    /// The changes of the graphs that differ, indexed by graph name (`None` for the default graph)
+    pub graphs: HashMap<Option<NamedOrBlankNode>, GraphDiff>,
+}
+
+impl DatasetDiff {
+    /// Checks if the two datasets are equal
+    pub fn is_empty(&self) -> bool {
+        self.graphs.is_empty()
+    }
+}
+
+/// The changes of a graph in a `DatasetDiff`
+#[derive(Eq, PartialEq, Debug, Clone, Default)]
+pub struct GraphDiff {
+    /// The added quads, sorted
+    pub added: Vec<Quad>,
+    /// The removed quads, sorted
+    pub removed: Vec<Quad>,
+}
@@ -0,0 +1,43 @@
+//! Allows to decode the values of literals with custom [datatypes](https://www.w3.org/TR/rdf11-concepts/#section-Datatypes)
+use model::data::*;
+use std::any::Any;
+use std::collections::HashMap;
+
+type DatatypeParser = Box<dyn Fn(&str) -> Option<Box<dyn Any + Send + Sync>> + Send + Sync>;
+
+/// Maps datatype IRIs to the functions parsing their lexical forms
+///
+/// It is used by `Literal::as_custom` to decode the literals with datatypes like `geo:wktLiteral`.
+#[derive(Default)]
+pub struct DatatypeRegistry {
+    parsers: HashMap<String, DatatypeParser>,
+}
+
+impl DatatypeRegistry {
+    /// Registers the parser of a datatype, replacing the previous parser of this datatype.
+    /// The parser should return None if the lexical form is invalid.
+    pub fn register<T: Any + Send + Sync>(
+        &mut self,
+        datatype: &NamedNode,
+        parser: impl Fn(&str) -> Option<T> + Send + Sync + 'static,
+    ) {
+        self.parsers.insert(
+            datatype.value().to_owned(),
+            Box::new(move |value| {
+                parser(value).map(|value| Box::new(value) as Box<dyn Any + Send + Sync>)
+            }),
+        );
+    }
+
+    /// Checks if a parser is registered for the datatype
+    pub fn contains(&self, datatype: &NamedNode) -> bool {
+        self.parsers.contains_key(datatype.value())
+    }
+
+    /// Parses the literal with the parser of its datatype
+    ///
+    /// Returns None if there is no parser for the literal datatype or if the lexical form is invalid.
+    pub fn parse(&self, literal: &Literal) -> Option<Box<dyn Any + Send + Sync>> {
+        self.parsers.get(literal.datatype().value())?(literal.value())
+    }
+}
@@ -0,0 +1,1128 @@
+//! Implements in-memory [RDF graphs](https://www.w3.org/TR/rdf11-concepts/#dfn-rdf-graph)
+use model::data::*;
+use model::iri::is_absolute_iri;
+use model::shapes::*;
+use model::vocab::owl;
+use model::vocab::rdf;
+use model::vocab::rdfs;
+use std::collections::BTreeMap;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::iter;
+use std::iter::FromIterator;
+use std::mem::size_of;
+
+/// An in-memory [RDF graph](https://www.w3.org/TR/rdf11-concepts/#dfn-rdf-graph)
+#[derive(Debug, Clone, Default)]
+pub struct Graph {
+    triples: HashSet<Triple>,
+    subjects: HashMap<NamedOrBlankNode, HashSet<Triple>>,
+}
+
+impl Graph {
+    /// Builds an empty graph whose triple set can hold `capacity` triples without reallocating
+    ///
+    /// The subject index still grows on insertion: the number of distinct subjects is not known from a triple count.
+    pub fn with_capacity(capacity: usize) -> Self {
+        Graph {
+            triples: HashSet::with_capacity(capacity),
+            subjects: HashMap::default(),
+        }
+    }
+
+    /// Adds a triple to the graph and returns false if it was already in it
+    pub fn insert(&mut self, triple: Triple) -> bool {
+        if !self.triples.insert(triple.clone()) {
+            return false;
+        }
+        self.subjects
+            .entry(triple.subject().clone())
+            .or_default()
+            .insert(triple);
+        true
+    }
+
+    /// Adds triples to the graph and returns for each of them if it was not already in the graph
+    ///
+    /// A triple repeated in `triples` is only new the first time.
+    pub fn insert_all<I: IntoIterator<Item = Triple>>(&mut self, triples: I) -> Vec<bool> {
+        triples
+            .into_iter()
+            .map(|triple| self.insert(triple))
+            .collect()
+    }
+
+    /// Removes a triple from the graph and returns false if it was not in it
+    pub fn remove(&mut self, triple: &Triple) -> bool {
+        if !self.triples.remove(triple) {
+            return false;
+        }
+        let is_subject_empty = match self.subjects.get_mut(triple.subject()) {
+            Some(triples) => {
+                triples.remove(triple);
+                triples.is_empty()
+            }
+            None => false,
+        };
+        if is_subject_empty {
+            self.subjects.remove(triple.subject());
+        }
+        true
+    }
+
+    /// Removes the triples whose subject and object are the same node, like `ex:x owl:sameAs ex:x`, and returns their number
+    pub fn remove_self_loops(&mut self) -> usize {
+        let self_loops: Vec<Triple> = self
+            .triples
+            .iter()
+            .filter(|triple| match (triple.subject(), triple.object()) {
+                (NamedOrBlankNode::NamedNode(subject), Term::NamedNode(object)) => {
+                    subject == object
+                }
+                (NamedOrBlankNode::BlankNode(subject), Term::BlankNode(object)) => {
+                    subject == object
+                }
+                _ => false,
+            })
+            .cloned()
+            .collect();
+        for triple in &self_loops {
+            self.remove(triple);
+        }
+        self_loops.len()
+    }
+
+    pub fn contains(&self, triple: &Triple) -> bool {
+        self.triples.contains(triple)
+    }
+
+    /// The number of triples in the graph
+    pub fn len(&self) -> usize {
+        self.triples.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.triples.is_empty()
+    }
+
+    /// Checks if the graph contains a triple matching the pattern, `None` matching any term
+    ///
+    /// It stops at the first match and only looks at the triples of the subject if it is bound.
+    pub fn contains_pattern(
+        &self,
+        subject: Option<&NamedOrBlankNode>,
+        predicate: Option<&NamedNode>,
+        object: Option<&Term>,
+    ) -> bool {
+        let matches = |triple: &Triple| {
+            predicate.is_none_or(|p| triple.predicate() == p)
+                && object.is_none_or(|o| triple.object() == o)
+        };
+        match subject {
+            Some(subject) => self
+                .subjects
+                .get(subject)
+                .is_some_and(|triples| triples.iter().any(matches)),
+            None => self.triples.iter().any(matches),
+        }
+    }
+
+    /// Returns the distinct predicates of the triples having the given subject
+    pub fn predicates_of(&self, subject: &NamedOrBlankNode) -> impl Iterator<Item = &NamedNode> {
+        let predicates: HashSet<&NamedNode> = self
+            .subjects
+            .get(subject)
+            .into_iter()
+            .flat_map(|triples| triples.iter().map(|t| t.predicate()))
+            .collect();
+        predicates.into_iter()
+    }
+
+    /// Returns the triples having the given node as subject or as object
+    ///
+    /// A triple having the node both as subject and object is only returned once.
+    pub fn triples_mentioning(&self, node: &NamedOrBlankNode) -> impl Iterator<Item = &Triple> {
+        let subject = node.clone();
+        let object = Term::from(node.clone());
+        let as_subject = self.subjects.get(node).into_iter().flatten();
+        let as_object = self
+            .triples
+            .iter()
+            .filter(move |triple| *triple.object() == object && *triple.subject() != subject);
+        as_subject.chain(as_object)
+    }
+
+    /// Returns the triples whose object value is the given string, whatever the kind of the object
+    ///
+    /// This is a loose text search: IRIs, blank node identifiers and literal lexical forms are compared,
+    /// and the datatypes and language tags of the literals are ignored.
+    pub fn triples_with_object_value<'a>(
+        &'a self,
+        value: &'a str,
+    ) -> impl Iterator<Item = &'a Triple> {
+        self.triples
+            .iter()
+            .filter(move |triple| triple.object().value() == value)
+    }
+
+    /// Returns the number of triples having the node as subject
+    pub fn out_degree(&self, node: &NamedOrBlankNode) -> usize {
+        self.subjects.get(node).map_or(0, HashSet::len)
+    }
+
+    /// Returns the number of triples having the node as object
+    pub fn in_degree(&self, node: &NamedOrBlankNode) -> usize {
+        let node = Term::from(node.clone());
+        self.triples
+            .iter()
+            .filter(|triple| *triple.object() == node)
+            .count()
+    }
+
+    /// Returns the number of nodes for each degree, the degree of a node being the sum of its in-degree and out-degree
+    ///
+    /// The nodes are the IRIs and blank nodes used as subject or object. Literals are not counted as nodes.
+    pub fn degree_histogram(&self) -> BTreeMap<usize, usize> {
+        let mut degrees: HashMap<(TermKind, &str), usize> = HashMap::default();
+        for triple in &self.triples {
+            let subject = triple.subject();
+            *degrees
+                .entry((subject.kind(), subject.value()))
+                .or_default() += 1;
+            let object = triple.object();
+            if object.kind() != TermKind::Literal {
+                *degrees.entry((object.kind(), object.value())).or_default() += 1;
+            }
+        }
+        let mut histogram = BTreeMap::default();
+        for degree in degrees.values() {
+            *histogram.entry(*degree).or_default() += 1;
+        }
+        histogram
+    }
+
+    /// Returns diagnostics about the graph storage
+    pub fn index_stats(&self) -> IndexStats {
+        let strings_size: usize = self.triples.iter().map(triple_strings_size).sum();
+        let subject_index_triples: usize = self.subjects.values().map(|t| t.capacity()).sum();
+        IndexStats {
+            triple_count: self.triples.len(),
+            subject_index_entries: self.subjects.len(),
+            estimated_bytes: (self.triples.capacity() + subject_index_triples) * size_of::<Triple>()
+                + self.subjects.capacity()
+                    * size_of::<(NamedOrBlankNode, HashSet<Triple>)>()
+                // The triples of the subject index share the term strings of the triple set
+                + strings_size,
+        }
+    }
+
+    /// Returns the distinct subjects that have the type `class`, i.e. a `rdf:type` triple with `class` as object
+    pub fn instances_of<'a>(
+        &'a self,
+        class: &'a NamedNode,
+    ) -> impl Iterator<Item = &'a NamedOrBlankNode> + 'a {
+        self.subjects
+            .iter()
+            .filter(move |(_, triples)| {
+                triples.iter().any(|t| {
+                    t.predicate().value() == rdf::TYPE
+                        && matches!(t.object(), Term::NamedNode(node) if node == class)
+                })
+            })
+            .map(|(subject, _)| subject)
+    }
+
+    /// Returns the types of `subject`, i.e. the objects of the `rdf:type` triples having it as subject
+    pub fn types_of<'a>(
+        &'a self,
+        subject: &NamedOrBlankNode,
+    ) -> impl Iterator<Item = &'a Term> + 'a {
+        self.subjects
+            .get(subject)
+            .into_iter()
+            .flat_map(|triples| triples.iter())
+            .filter(|t| t.predicate().value() == rdf::TYPE)
+            .map(|t| t.object())
+    }
+
+    /// Returns the items of the [RDF collection](https://www.w3.org/TR/rdf11-mt/#rdf-collections) starting at `head`
+    ///
+    /// Returns None if the list is not well formed: if a node does not have exactly one `rdf:first` and one `rdf:rest`,
+    /// if a `rdf:rest` value is a literal or if the list contains a cycle.
+    pub fn list_items(&self, head: &NamedOrBlankNode) -> Option<Vec<&Term>> {
+        let mut items = Vec::default();
+        let mut visited = HashSet::new();
+        let mut current = head.clone();
+        while !matches!(&current, NamedOrBlankNode::NamedNode(node) if node.value() == rdf::NIL) {
+            if !visited.insert(current.clone()) {
+                return None; // Cycle
+            }
+            let mut first = None;
+            let mut rest = None;
+            for triple in self.subjects.get(&current)? {
+                let value = match triple.predicate().value() {
+                    rdf::FIRST => &mut first,
+                    rdf::REST => &mut rest,
+                    _ => continue,
+                };
+                if value.replace(triple.object()).is_some() {
+                    return None; // Multiple values
+                }
+            }
+            items.push(first?);
+            current = match rest? {
+                Term::NamedNode(node) => node.clone().into(),
+                Term::BlankNode(node) => node.clone().into(),
+                Term::Literal(_) => return None,
+            };
+        }
+        Some(items)
+    }
+
+    /// Checks if `head` is the start of a well formed [RDF collection](https://www.w3.org/TR/rdf11-mt/#rdf-collections), following the rules of `list_items`
+    pub fn is_well_formed_list(&self, head: &NamedOrBlankNode) -> bool {
+        self.list_items(head).is_some()
+    }
+
+    /// Iterates on the graph triples in an arbitrary order
+    pub fn iter(&self) -> impl Iterator<Item = &Triple> {
+        self.triples.iter()
+    }
+
+    /// Splits the graph triples into batches of `size` triples, the last one being possibly smaller
+    ///
+    /// The triples are in an arbitrary order, like with `iter`.
+    ///
+    /// Panics if `size` is 0.
+    pub fn batches(&self, size: usize) -> impl Iterator<Item = Vec<&Triple>> {
+        assert!(size > 0, "The batch size should not be 0");
+        let mut triples = self.triples.iter();
+        iter::from_fn(move || {
+            let batch: Vec<_> = triples.by_ref().take(size).collect();
+            if batch.is_empty() {
+                None
+            } else {
+                Some(batch)
+            }
+        })
+    }
+
+    /// Iterates on the graph triples as `TripleRef` in an arbitrary order
+    pub fn iter_refs(&self) -> impl Iterator<Item = TripleRef<'_>> {
+        self.triples.iter().map(TripleRef::from)
+    }
+
+    /// Iterates on the graph triples ordered by subject, predicate and object
+    ///
+    /// The order only depends on the triples so two graphs with the same triples are always iterated in the same order.
+    pub fn iter_sorted(&self) -> impl Iterator<Item = &Triple> {
+        let mut triples: Vec<&Triple> = self.triples.iter().collect();
+        triples.sort();
+        triples.into_iter()
+    }
+
+    /// Returns the objects of the triples with the given predicate grouped by subject
+    ///
+    /// It is useful to build property indexes like `rdfs:label` dictionaries.
+    pub fn predicate_map(&self, predicate: &NamedNode) -> HashMap<NamedOrBlankNode, Vec<Term>> {
+        let mut map: HashMap<NamedOrBlankNode, Vec<Term>> = HashMap::default();
+        for triple in self.triples.iter().filter(|t| t.predicate() == predicate) {
+            map.entry(triple.subject().clone())
+                .or_default()
+                .push(triple.object().clone());
+        }
+        map
+    }
+
+    /// Returns for each subject its outgoing edges, the (predicate, object) pairs of the triples it is the subject of
+    ///
+    /// The edges of each subject are sorted.
+    pub fn to_adjacency(&self) -> HashMap<NamedOrBlankNode, Vec<(NamedNode, Term)>> {
+        self.subjects
+            .iter()
+            .map(|(subject, triples)| {
+                let mut edges: Vec<_> = triples
+                    .iter()
+                    .map(|triple| (triple.predicate().clone(), triple.object().clone()))
+                    .collect();
+                edges.sort();
+                (subject.clone(), edges)
+            })
+            .collect()
+    }
+
+    /// Counts for each predicate the kinds of its objects
+    ///
+    /// It is useful to infer if a property is object-valued or datatype-valued.
+    pub fn predicate_ranges(&self) -> HashMap<NamedNode, ObjectKindSummary> {
+        let mut ranges: HashMap<NamedNode, ObjectKindSummary> = HashMap::default();
+        for triple in &self.triples {
+            let summary = ranges.entry(triple.predicate().clone()).or_default();
+            match triple.object() {
+                Term::NamedNode(_) => summary.named_nodes += 1,
+                Term::BlankNode(_) => summary.blank_nodes += 1,
+                Term::Literal(literal) => {
+                    summary.literals += 1;
+                    *summary
+                        .datatypes
+                        .entry(literal.datatype().clone())
+                        .or_default() += 1;
+                }
+            }
+        }
+        ranges
+    }
+
+    /// Returns true if no subject has more than one object for the given predicate
+    ///
+    /// It is the case of the predicates that are not used in the graph.
+    pub fn is_functional(&self, predicate: &NamedNode) -> bool {
+        self.max_cardinality(predicate) <= 1
+    }
+
+    /// Returns the largest number of objects a subject has for the given predicate
+    pub fn max_cardinality(&self, predicate: &NamedNode) -> usize {
+        self.subjects
+            .values()
+            .map(|triples| {
+                triples
+                    .iter()
+                    .filter(|triple| triple.predicate() == predicate)
+                    .count()
+            })
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Splits the graph into its weakly connected components
+    ///
+    /// Two triples are in the same component if they share a subject or an IRI or blank node object, directly or through other triples.
+    /// Predicates and literals do not connect triples.
+    /// The components are ordered by their smallest triple.
+    pub fn connected_components(&self) -> Vec<Graph> {
+        // Union-find on the subject and object nodes
+        let mut node_ids: HashMap<(TermKind, &str), usize> = HashMap::default();
+        let mut parents: Vec<usize> = Vec::default();
+        let mut node_id = |key, parents: &mut Vec<usize>| {
+            *node_ids.entry(key).or_insert_with(|| {
+                parents.push(parents.len());
+                parents.len() - 1
+            })
+        };
+        let mut subject_ids = Vec::with_capacity(self.triples.len());
+        for triple in &self.triples {
+            let subject = triple.subject();
+            let subject_id = node_id((subject.kind(), subject.value()), &mut parents);
+            let object = triple.object();
+            if object.kind() != TermKind::Literal {
+                let object_id = node_id((object.kind(), object.value()), &mut parents);
+                let subject_root = find_root(&mut parents, subject_id);
+                let object_root = find_root(&mut parents, object_id);
+                parents[object_root] = subject_root;
+            }
+            subject_ids.push((triple, subject_id));
+        }
+
+        let mut components: HashMap<usize, Graph> = HashMap::default();
+        for (triple, subject_id) in subject_ids {
+            components
+                .entry(find_root(&mut parents, subject_id))
+                .or_default()
+                .insert(triple.clone());
+        }
+        let mut components: Vec<Graph> = components.into_values().collect();
+        components.sort_by_cached_key(|component| component.iter().min().cloned());
+        components
+    }
+
+    /// Returns the statements described using the [RDF reification vocabulary](https://www.w3.org/TR/rdf11-mt/#reification) with the triples they describe
+    ///
+    /// A statement node is a subject with exactly one `rdf:subject`, one `rdf:predicate` and one `rdf:object`, the `rdf:type rdf:Statement` triple being optional.
+    /// Incomplete statements and statements whose `rdf:subject` is a literal or whose `rdf:predicate` is not an IRI are skipped.
+    /// The statements are ordered by statement node.
+    pub fn reified_statements(&self) -> impl Iterator<Item = (NamedOrBlankNode, Triple)> + '_ {
+        let mut statements: Vec<(&NamedOrBlankNode, &HashSet<Triple>)> =
+            self.subjects.iter().collect();
+        statements.sort_by_key(|(node, _)| *node);
+        statements.into_iter().filter_map(|(node, triples)| {
+            let component = |property: &str| {
+                let mut values = triples
+                    .iter()
+                    .filter(|triple| triple.predicate().value() == property)
+                    .map(|triple| triple.object());
+                match (values.next(), values.next()) {
+                    (Some(value), None) => Some(value),
+                    _ => None,
+                }
+            };
+            let subject = match component(rdf::SUBJECT)? {
+                Term::NamedNode(node) => NamedOrBlankNode::from(node.clone()),
+                Term::BlankNode(node) => NamedOrBlankNode::from(node.clone()),
+                Term::Literal(_) => return None,
+            };
+            let predicate = component(rdf::PREDICATE)?.as_predicate()?.clone();
+            let object = component(rdf::OBJECT)?.clone();
+            Some((node.clone(), Triple::from((subject, predicate, object))))
+        })
+    }
+
+    /// Returns the distinct blank nodes used as subject or object of the graph triples
+    pub fn blank_nodes(&self) -> impl Iterator<Item = &BlankNode> {
+        let mut blank_nodes = HashSet::new();
+        for triple in &self.triples {
+            if let NamedOrBlankNode::BlankNode(node) = triple.subject() {
+                blank_nodes.insert(node);
+            }
+            if let Term::BlankNode(node) = triple.object() {
+                blank_nodes.insert(node);
+            }
+        }
+        blank_nodes.into_iter()
+    }
+
+    /// Returns the distinct IRIs of the graph without scheme, sorted
+    ///
+    /// The subjects, predicates, objects and literal datatypes are checked.
+    /// It allows to find relative IRIs that were not resolved against a base IRI.
+    pub fn find_relative_iris(&self) -> Vec<&NamedNode> {
+        let relative_iris: HashSet<&NamedNode> = self
+            .named_nodes()
+            .filter(|node| !is_absolute_iri(node.value()))
+            .collect();
+        let mut relative_iris: Vec<&NamedNode> = relative_iris.into_iter().collect();
+        relative_iris.sort();
+        relative_iris
+    }
+
+    /// Returns the distinct datatypes of the graph literals that are not in `known`, sorted
+    ///
+    /// The simple literals have the xsd:string datatype and the language-tagged strings the rdf:langString datatype,
+    /// so they are reported if these datatypes are not in `known`.
+    /// It allows to find typos in datatype IRIs.
+    pub fn find_unknown_datatypes(&self, known: &HashSet<NamedNode>) -> Vec<&NamedNode> {
+        let unknown: HashSet<&NamedNode> = self
+            .literal_datatypes()
+            .filter(|datatype| !known.contains(*datatype))
+            .collect();
+        let mut unknown: Vec<&NamedNode> = unknown.into_iter().collect();
+        unknown.sort();
+        unknown
+    }
+
+    /// Returns the distinct datatypes of the graph literals
+    ///
+    /// The simple literals have the xsd:string datatype and the language-tagged strings the rdf:langString datatype.
+    pub fn datatypes_used(&self) -> HashSet<NamedNode> {
+        self.literal_datatypes().cloned().collect()
+    }
+
+    /// Iterates on the datatypes of the object literals, with repetitions
+    fn literal_datatypes(&self) -> impl Iterator<Item = &NamedNode> {
+        self.triples
+            .iter()
+            .filter_map(|triple| match triple.object() {
+                Term::Literal(literal) => Some(literal.datatype()),
+                _ => None,
+            })
+    }
+
+    /// Returns the distinct namespaces of the graph IRIs, including the literal datatypes
+    ///
+    /// The namespace of an IRI is its prefix up to its last `#` or `/`, like `http://example.com/` for `http://example.com/foo`.
+    /// The IRIs without `#` or `/` are ignored.
+    pub fn namespaces(&self) -> HashSet<String> {
+        self.named_nodes()
+            .filter_map(|node| {
+                let iri = node.value();
+                iri.rfind(['#', '/']).map(|i| iri[..=i].to_owned())
+            })
+            .collect()
+    }
+
+    /// Iterates on the IRIs used as subject, predicate, object or literal datatype, with repetitions
+    fn named_nodes(&self) -> impl Iterator<Item = &NamedNode> {
+        self.triples.iter().flat_map(|triple| {
+            let subject = match triple.subject() {
+                NamedOrBlankNode::NamedNode(node) => Some(node),
+                NamedOrBlankNode::BlankNode(_) => None,
+            };
+            let object = match triple.object() {
+                Term::NamedNode(node) => Some(node),
+                Term::Literal(literal) => Some(literal.datatype()),
+                Term::BlankNode(_) => None,
+            };
+            subject
+                .into_iter()
+                .chain(Some(triple.predicate()))
+                .chain(object)
+        })
+    }
+
+    /// Validates the instances of the shapes classes against the shapes constraints and returns the violations
+    pub fn validate_shapes(&self, shapes: &[Shape]) -> Vec<Violation> {
+        let mut violations = Vec::default();
+        for shape in shapes {
+            let focus_nodes = self.triples.iter().filter(|t| {
+                t.predicate().value() == rdf::TYPE && *t.object() == Term::from(shape.class.clone())
+            });
+            for focus_node in focus_nodes.map(|t| t.subject()) {
+                for property in &shape.properties {
+                    let values: Vec<&Term> = self
+                        .triples
+                        .iter()
+                        .filter(|t| {
+                            t.subject() == focus_node && *t.predicate() == property.predicate
+                        })
+                        .map(|t| t.object())
+                        .collect();
+                    let mut violation = |kind| {
+                        violations.push(Violation {
+                            focus_node: focus_node.clone(),
+                            predicate: property.predicate.clone(),
+                            kind,
+                        })
+                    };
+                    if values.len() < property.min_count {
+                        violation(ViolationKind::MinCount {
+                            expected: property.min_count,
+                            actual: values.len(),
+                        });
+                    }
+                    if let Some(max_count) = property.max_count {
+                        if values.len() > max_count {
+                            violation(ViolationKind::MaxCount {
+                                expected: max_count,
+                                actual: values.len(),
+                            });
+                        }
+                    }
+                    if let Some(datatype) = &property.datatype {
+                        for value in values {
+                            match value {
+                                Term::Literal(literal) if literal.datatype() == datatype => (),
+                                value => violation(ViolationKind::Datatype {
+                                    expected: datatype.clone(),
+                                    value: value.clone(),
+                                }),
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        violations
+    }
+
+    /// Iterates on the graph subjects with all the triples having them as subject
+    ///
+    /// Subjects and triples are returned in the order of `iter_sorted`.
+    pub fn iter_by_subject(&self) -> impl Iterator<Item = (&NamedOrBlankNode, Vec<&Triple>)> {
+        let mut groups: Vec<(&NamedOrBlankNode, Vec<&Triple>)> = Vec::default();
+        for triple in self.iter_sorted() {
+            match groups.last_mut() {
+                Some((subject, triples)) if *subject == triple.subject() => triples.push(triple),
+                _ => groups.push((triple.subject(), vec![triple])),
+            }
+        }
+        groups.into_iter()
+    }
+
+    /// Adds the `rdfs:subClassOf` triples implied by the transitivity of `rdfs:subClassOf`
+    ///
+    /// This implements the [rdfs11](https://www.w3.org/TR/rdf11-mt/#patterns-of-rdfs-entailment-informative) entailment rule.
+    /// Cycles are supported: the classes of a cycle become subclasses of each other and of themselves.
+    pub fn subclass_closure(&mut self) {
+        let mut sub_class_of = None;
+        let mut super_classes: HashMap<&NamedOrBlankNode, Vec<NamedOrBlankNode>> =
+            HashMap::default();
+        for triple in &self.triples {
+            if triple.predicate().value() != rdfs::SUB_CLASS_OF {
+                continue;
+            }
+            sub_class_of = Some(triple.predicate());
+            let super_class = match triple.object() {
+                Term::NamedNode(node) => node.clone().into(),
+                Term::BlankNode(node) => node.clone().into(),
+                Term::Literal(_) => continue,
+            };
+            super_classes
+                .entry(triple.subject())
+                .or_default()
+                .push(super_class);
+        }
+        let sub_class_of = match sub_class_of {
+            Some(sub_class_of) => sub_class_of.clone(),
+            None => return,
+        };
+
+        let mut new_triples = Vec::default();
+        for class in super_classes.keys() {
+            let mut visited: HashSet<&NamedOrBlankNode> = HashSet::default();
+            let mut to_visit: Vec<&NamedOrBlankNode> = super_classes[class].iter().collect();
+            while let Some(super_class) = to_visit.pop() {
+                if visited.insert(super_class) {
+                    if let Some(next) = super_classes.get(super_class) {
+                        to_visit.extend(next);
+                    }
+                }
+            }
+            for super_class in visited {
+                new_triples.push(Triple::from((
+                    (*class).clone(),
+                    sub_class_of.clone(),
+                    super_class.clone(),
+                )));
+            }
+        }
+        self.extend(new_triples);
+    }
+
+    /// Checks if the two graphs contain exactly the same triples
+    ///
+    /// Blank nodes are compared using their ids, so this is only a valid graph equality if the two graphs share the same blank nodes.
+    /// Graphs that are equal up to a renaming of their blank nodes are [isomorphic](https://www.w3.org/TR/rdf11-concepts/#dfn-graph-isomorphism) but not ground equal.
+    pub fn ground_eq(&self, other: &Graph) -> bool {
+        self.triples == other.triples
+    }
+
+    /// Rewrites the IRIs of the graph for which `f` returns a new IRI
+    ///
+    /// IRIs are rewritten in all the subject, predicate, object and literal datatype positions.
+    pub fn map_iris(&mut self, f: impl Fn(&str) -> Option<String>) {
+        let data_factory = DataFactory::default();
+        let map_named_node = |node: &NamedNode| match f(node.value()) {
+            Some(iri) => data_factory.named_node(iri),
+            None => node.clone(),
+        };
+        *self = self
+            .triples
+            .iter()
+            .map(|triple| {
+                let subject: NamedOrBlankNode = match triple.subject() {
+                    NamedOrBlankNode::NamedNode(node) => map_named_node(node).into(),
+                    NamedOrBlankNode::BlankNode(node) => node.clone().into(),
+                };
+                let object: Term = match triple.object() {
+                    Term::NamedNode(node) => map_named_node(node).into(),
+                    Term::BlankNode(node) => node.clone().into(),
+                    Term::Literal(Literal::TypedLiteral { value, datatype }) => data_factory
+                        .typed_literal(value.to_string(), map_named_node(datatype))
+                        .into(),
+                    Term::Literal(literal) => literal.clone().into(),
+                };
+                data_factory.triple(subject, map_named_node(triple.predicate()), object)
+            })
+            .collect();
+    }
+
+    /// Replaces the blank nodes by [skolem IRIs](https://www.w3.org/TR/rdf11-concepts/#section-skolemization) like `{base}/.well-known/genid/{id}`
+    pub fn skolemize(&mut self, base: &str) {
+        let data_factory = DataFactory::default();
+        let prefix = skolem_iri_prefix(base);
+        self.map_nodes(|node| match node {
+            NamedOrBlankNode::BlankNode(node) => data_factory
+                .named_node(format!("{}{}", prefix, node.value()))
+                .into(),
+            node => node.clone(),
+        })
+    }
+
+    /// Replaces the skolem IRIs created by `skolemize` with the same base by the blank nodes they were built from
+    pub fn deskolemize(&mut self, base: &str) {
+        let data_factory = DataFactory::default();
+        let prefix = skolem_iri_prefix(base);
+        self.map_nodes(|node| match node {
+            NamedOrBlankNode::NamedNode(node) if node.value().starts_with(&prefix) => data_factory
+                .blank_node(&node.value()[prefix.len()..])
+                .into(),
+            node => node.clone(),
+        })
+    }
+
+    /// Renames the blank nodes `b0`, `b1`... in an order only depending on the graph triples
+    ///
+    /// The blank nodes are ordered by the sorted list of the triples they appear in, where the blank nodes are replaced by placeholders,
+    /// and then by their former labels if these lists are equal, the numbers ending the labels being compared by value so that `b2` comes before `b10`.
+    /// So relabeling the same graph always gives the same labels and relabeling a relabeled graph does not change it.
+    /// It is not a canonicalization: two isomorphic graphs only get the same labels if their blank nodes have distinct neighborhoods.
+    pub fn relabel_blank_nodes_deterministically(&mut self) {
+        let mut neighborhoods: HashMap<&BlankNode, Vec<String>> = HashMap::default();
+        for triple in &self.triples {
+            let subject = match triple.subject() {
+                NamedOrBlankNode::BlankNode(node) => Some(node),
+                NamedOrBlankNode::NamedNode(_) => None,
+            };
+            let object = match triple.object() {
+                Term::BlankNode(node) => Some(node),
+                _ => None,
+            };
+            // A triple with the same blank node as subject and object is only described once
+            for node in subject
+                .into_iter()
+                .chain(object.filter(|object| Some(*object) != subject))
+            {
+                let placeholder =
+                    |term: &BlankNode| if term == node { "_:self" } else { "_:other" };
+                let subject = subject.map_or_else(
+                    || triple.subject().to_string(),
+                    |s| placeholder(s).to_owned(),
+                );
+                let object = object.map_or_else(
+                    || triple.object().to_string(),
+                    |o| placeholder(o).to_owned(),
+                );
+                neighborhoods.entry(node).or_default().push(format!(
+                    "{} {} {}",
+                    subject,
+                    triple.predicate(),
+                    object
+                ));
+            }
+        }
+        let mut nodes: Vec<(Vec<String>, &BlankNode)> = neighborhoods
+            .into_iter()
+            .map(|(node, mut neighborhood)| {
+                neighborhood.sort();
+                (neighborhood, node)
+            })
+            .collect();
+        nodes.sort_by(|(left_neighborhood, left), (right_neighborhood, right)| {
+            left_neighborhood.cmp(right_neighborhood).then_with(|| {
+                numbered_label_key(left.value()).cmp(&numbered_label_key(right.value()))
+            })
+        });
+        let data_factory = DataFactory::default();
+        let labels: HashMap<BlankNode, BlankNode> = nodes
+            .into_iter()
+            .enumerate()
+            .map(|(i, (_, node))| (node.clone(), data_factory.blank_node(format!("b{}", i))))
+            .collect();
+        self.map_nodes(|node| match node {
+            NamedOrBlankNode::BlankNode(node) => labels[node].clone().into(),
+            node => node.clone(),
+        })
+    }
+
+    /// Returns the schema triples of the graph using the default RDFS and OWL `SchemaFilter`
+    ///
+    /// It allows to extract the terminology of an ontology (its TBox) from the instance data.
+    pub fn filter_schema(&self) -> Graph {
+        self.filter_schema_with(&SchemaFilter::default())
+    }
+
+    /// Returns the triples whose predicate is one of the filter predicates
+    /// and the `rdf:type` triples whose object is one of the filter classes
+    pub fn filter_schema_with(&self, filter: &SchemaFilter) -> Graph {
+        self.triples
+            .iter()
+            .filter(|triple| {
+                let predicate = triple.predicate().value();
+                filter.predicates.contains(predicate)
+                    || (predicate == rdf::TYPE
+                        && match triple.object() {
+                            Term::NamedNode(class) => filter.classes.contains(class.value()),
+                            _ => false,
+                        })
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Adds the triples of an other graph, following the [RDF merge](https://www.w3.org/TR/rdf11-mt/#shared-blank-nodes-unions-and-merges) semantics
+    ///
+    /// The blank nodes of `other` that are also used in this graph are renamed so that they are not conflated.
+    pub fn merge(&mut self, mut other: Graph) {
+        let used_ids: HashSet<String> = self
+            .blank_nodes()
+            .chain(other.blank_nodes())
+            .map(|node| node.value().to_owned())
+            .collect();
+        let self_ids: HashSet<&str> = self.blank_nodes().map(|node| node.value()).collect();
+        let mut renaming: HashMap<String, BlankNode> = HashMap::default();
+        let data_factory = DataFactory::default();
+        let mut counter = 0;
+        for node in other.blank_nodes() {
+            if self_ids.contains(node.value()) {
+                let new_id = loop {
+                    counter += 1;
+                    let id = format!("{}_{}", node.value(), counter);
+                    if !used_ids.contains(&id) {
+                        break id;
+                    }
+                };
+                renaming.insert(node.value().to_owned(), data_factory.blank_node(new_id));
+            }
+        }
+        if !renaming.is_empty() {
+            other.map_nodes(|node| match node {
+                NamedOrBlankNode::BlankNode(node) => match renaming.get(node.value()) {
+                    Some(new_node) => new_node.clone().into(),
+                    None => node.clone().into(),
+                },
+                node => node.clone(),
+            });
+        }
+        self.extend(other.triples);
+    }
+
+    /// Rewrites the nodes in subject and object positions
+    fn map_nodes(&mut self, f: impl Fn(&NamedOrBlankNode) -> NamedOrBlankNode) {
+        let data_factory = DataFactory::default();
+        *self = self
+            .triples
+            .iter()
+            .map(|triple| {
+                let object: Term = match triple.object() {
+                    Term::NamedNode(node) => f(&node.clone().into()).into(),
+                    Term::BlankNode(node) => f(&node.clone().into()).into(),
+                    Term::Literal(literal) => literal.clone().into(),
+                };
+                data_factory.triple(f(triple.subject()), triple.predicate().clone(), object)
+            })
+            .collect();
+    }
+}
+
+/// Splits a label into its prefix and its ending number, without leading zeros, to order the labels like `b2` before `b10`
+///
+/// The number is compared by its length then by its digits so it can not overflow, and the label itself breaks the remaining ties like `b01` and `b1`.
+fn numbered_label_key(label: &str) -> (&str, usize, &str, &str) {
+    let prefix = label.trim_end_matches(|c: char| c.is_ascii_digit());
+    let number = label[prefix.len()..].trim_start_matches('0');
+    (prefix, number.len(), number, label)
+}
+
+/// Returns the root of the union-find tree containing `node`, compressing the path to it
+fn find_root(parents: &mut [usize], mut node: usize) -> usize {
+    while parents[node] != node {
+        parents[node] = parents[parents[node]];
+        node = parents[node];
+    }
+    node
+}
+
+/// The kinds of the objects of a predicate returned by `Graph::predicate_ranges`
+#[derive(Eq, PartialEq, Debug, Clone, Default)]
+pub struct ObjectKindSummary {
+    /// The number of IRI objects
+    pub named_nodes: usize,
+    /// The number of blank node objects
+    pub blank_nodes: usize,
+    /// The number of literal objects
+    pub literals: usize,
+    /// The number of literal objects per datatype
+    pub datatypes: HashMap<NamedNode, usize>,
+}
+
+impl ObjectKindSummary {
+    /// Returns true if all the objects are IRIs or blank nodes
+    pub fn is_object_valued(&self) -> bool {
+        self.literals == 0
+    }
+
+    /// Returns true if all the objects are literals
+    pub fn is_datatype_valued(&self) -> bool {
+        self.named_nodes == 0 && self.blank_nodes == 0
+    }
+}
+
+/// The vocabulary used by `Graph::filter_schema_with` to recognize schema triples
+///
+/// The default filter contains the RDFS and OWL predicates relating classes and properties
+/// and the RDFS and OWL classes of classes and properties.
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct SchemaFilter {
+    /// The IRIs of the predicates of the schema triples
+    pub predicates: HashSet<String>,
+    /// The IRIs of the classes whose `rdf:type` triples are schema triples
+    pub classes: HashSet<String>,
+}
+
+impl Default for SchemaFilter {
+    fn default() -> Self {
+        let predicates = [
+            rdfs::SUB_CLASS_OF,
+            rdfs::SUB_PROPERTY_OF,
+            rdfs::DOMAIN,
+            rdfs::RANGE,
+            owl::EQUIVALENT_CLASS,
+            owl::EQUIVALENT_PROPERTY,
+            owl::DISJOINT_WITH,
+            owl::INVERSE_OF,
+            owl::ON_PROPERTY,
+            owl::SOME_VALUES_FROM,
+            owl::ALL_VALUES_FROM,
+            owl::HAS_VALUE,
+            owl::CARDINALITY,
+            owl::MIN_CARDINALITY,
+            owl::MAX_CARDINALITY,
+            owl::UNION_OF,
+            owl::INTERSECTION_OF,
+            owl::COMPLEMENT_OF,
+            owl::IMPORTS,
+        ];
+        let classes = [
+            rdfs::CLASS,
+            rdfs::DATATYPE,
+            rdf::PROPERTY,
+            owl::CLASS,
+            owl::RESTRICTION,
+            owl::ONTOLOGY,
+            owl::OBJECT_PROPERTY,
+            owl::DATATYPE_PROPERTY,
+            owl::ANNOTATION_PROPERTY,
+            owl::FUNCTIONAL_PROPERTY,
+            owl::INVERSE_FUNCTIONAL_PROPERTY,
+            owl::TRANSITIVE_PROPERTY,
+            owl::SYMMETRIC_PROPERTY,
+        ];
+        SchemaFilter {
+            predicates: predicates.iter().map(|iri| iri.to_string()).collect(),
+            classes: classes.iter().map(|iri| iri.to_string()).collect(),
+        }
+    }
+}
+
+/// Diagnostics about the storage of a `Graph` returned by `Graph::index_stats`
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Hash)]
+pub struct IndexStats {
+    /// The number of triples in the graph
+    pub triple_count: usize,
+    /// The number of distinct subjects in the subject index
+    pub subject_index_entries: usize,
+    /// An estimation of the memory used by the triple set, the subject index and the term strings.
+    /// The hash tables overhead is not taken into account.
+    pub estimated_bytes: usize,
+}
+
+/// The prefix of the skolem IRIs built from `base`
+pub(crate) fn skolem_iri_prefix(base: &str) -> String {
+    format!("{}/.well-known/genid/", base.trim_end_matches('/'))
+}
+
+/// The size of the reference counts stored with the `Arc<str>` of the terms
+const ARC_COUNTS_SIZE: usize = 2 * size_of::<usize>();
+
+/// The number of bytes used by the strings of the triple terms, with the reference counts of the shared ones
+fn triple_strings_size(triple: &Triple) -> usize {
+    let object_size = match triple.object() {
+        Term::NamedNode(node) => ARC_COUNTS_SIZE + node.value().len(),
+        Term::BlankNode(node) => ARC_COUNTS_SIZE + node.value().len(),
+        Term::Literal(Literal::SimpleLiteral(value)) => ARC_COUNTS_SIZE + value.len(),
+        Term::Literal(Literal::LanguageTaggedString { value, language }) => {
+            ARC_COUNTS_SIZE + value.len() + language.len()
+        }
+        Term::Literal(Literal::TypedLiteral { value, datatype }) => {
+            2 * ARC_COUNTS_SIZE + value.len() + datatype.value().len()
+        }
+    };
+    2 * ARC_COUNTS_SIZE
+        + triple.subject().value().len()
+        + triple.predicate().value().len()
+        + object_size
+}
+
+impl FromIterator<Triple> for Graph {
+    fn from_iter<I: IntoIterator<Item = Triple>>(iter: I) -> Self {
+        let mut graph = Graph::default();
+        graph.extend(iter);
+        graph
+    }
+}
+
+/// Reserves room in the triple set for the lower bound of the iterator size hint
+impl Extend<Triple> for Graph {
+    fn extend<I: IntoIterator<Item = Triple>>(&mut self, iter: I) {
+        let iter = iter.into_iter();
+        self.triples.reserve(iter.size_hint().0);
+        for triple in iter {
+            self.insert(triple);
+        }
+    }
+}
+
+/// A change recorded by a `TransactionalGraph`
+#[derive(Eq, PartialEq, Debug, Clone, Hash)]
+enum GraphChange {
+    Insert(Triple),
+    Remove(Triple),
+}
+
+/// A `Graph` wrapper buffering the changes done during a transaction
+///
+/// The changes done after `begin` are only applied to the graph by `commit` and are discarded by `rollback`.
+/// Outside of a transaction the changes are applied directly.
+#[derive(Debug, Clone, Default)]
+pub struct TransactionalGraph {
+    graph: Graph,
+    changelog: Option<Vec<GraphChange>>,
+}
+
+impl TransactionalGraph {
+    pub fn new(graph: Graph) -> Self {
+        TransactionalGraph {
+            graph,
+            changelog: None,
+        }
+    }
+
+    /// Starts a transaction. It does nothing if a transaction is already started.
+    pub fn begin(&mut self) {
+        if self.changelog.is_none() {
+            self.changelog = Some(Vec::default());
+        }
+    }
+
+    pub fn is_in_transaction(&self) -> bool {
+        self.changelog.is_some()
+    }
+
+    /// Adds a triple to the graph, or to the changelog during a transaction
+    pub fn insert(&mut self, triple: Triple) {
+        match &mut self.changelog {
+            Some(changelog) => changelog.push(GraphChange::Insert(triple)),
+            None => {
+                self.graph.insert(triple);
+            }
+        }
+    }
+
+    /// Removes a triple from the graph, or records its removal in the changelog during a transaction
+    pub fn remove(&mut self, triple: &Triple) {
+        match &mut self.changelog {
+            Some(changelog) => changelog.push(GraphChange::Remove(triple.clone())),
+            None => {
+                self.graph.remove(triple);
+            }
+        }
+    }
+
+    /// Applies the changes of the current transaction in the order they were done and ends it
+    pub fn commit(&mut self) {
+        for change in self.changelog.take().unwrap_or_default() {
+            match change {
+                GraphChange::Insert(triple) => {
+                    self.graph.insert(triple);
+                }
+                GraphChange::Remove(triple) => {
+                    self.graph.remove(&triple);
+                }
+            }
+        }
+    }
+
+    /// Discards the changes of the current transaction and ends it
+    pub fn rollback(&mut self) {
+        self.changelog = None;
+    }
+
+    /// The graph with only the committed changes
+    pub fn graph(&self) -> &Graph {
+        &self.graph
+    }
+
+    /// Returns the graph with only the committed changes
+    pub fn into_inner(self) -> Graph {
+        self.graph
+    }
+}
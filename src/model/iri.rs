@@ -0,0 +1,288 @@
+//! Implements [IRI](https://www.ietf.org/rfc/rfc3987.txt) validation and [relative references resolution](https://tools.ietf.org/html/rfc3986#section-5)
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+
+/// An error raised when an IRI is invalid
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum IriError {
+    /// The IRI has no scheme so it is not absolute
+    MissingScheme(String),
+    /// The IRI contains a character that is not allowed in IRIs
+    InvalidCharacter(String, char),
+    /// The prefix of a prefixed name is not declared
+    UnknownPrefix(String),
+}
+
+impl fmt::Display for IriError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            IriError::MissingScheme(iri) => write!(f, "The IRI <{}> is not absolute", iri),
+            IriError::InvalidCharacter(iri, c) => {
+                write!(
+                    f,
+                    "The IRI <{}> contains the invalid character {:?}",
+                    iri, c
+                )
+            }
+            IriError::UnknownPrefix(prefix) => {
+                write!(f, "The prefix '{}:' is not declared", prefix)
+            }
+        }
+    }
+}
+
+impl Error for IriError {}
+
+/// Checks that the IRI is absolute and does not contain characters forbidden in IRIs
+pub fn validate_iri(iri: &str) -> Result<(), IriError> {
+    validate_iri_reference(iri)?;
+    if IriComponents::parse(iri).scheme.is_none() {
+        return Err(IriError::MissingScheme(iri.to_owned()));
+    }
+    Ok(())
+}
+
+/// Returns true if the IRI reference has a scheme and so is an absolute IRI
+pub fn is_absolute_iri(iri: &str) -> bool {
+    IriComponents::parse(iri).scheme.is_some()
+}
+
+/// Checks that the IRI reference, that may be relative, does not contain characters forbidden in IRIs
+pub fn validate_iri_reference(iri: &str) -> Result<(), IriError> {
+    match iri.chars().find(|c| is_forbidden_char(*c)) {
+        Some(c) => Err(IriError::InvalidCharacter(iri.to_owned(), c)),
+        None => Ok(()),
+    }
+}
+
+fn is_forbidden_char(c: char) -> bool {
+    matches!(
+        c,
+        '\u{00}'..='\u{20}' | '<' | '>' | '"' | '{' | '}' | '|' | '^' | '`' | '\\'
+    )
+}
+
+/// Resolves the reference against the base IRI following [RFC 3986](https://tools.ietf.org/html/rfc3986#section-5.2) and validates the result
+pub fn resolve_iri(reference: &str, base: &str) -> Result<String, IriError> {
+    validate_iri(base)?;
+    let base = IriComponents::parse(base);
+    let reference = IriComponents::parse(reference);
+
+    let mut target = IriComponents::default();
+    let path;
+    if reference.scheme.is_some() {
+        target.scheme = reference.scheme;
+        target.authority = reference.authority;
+        path = remove_dot_segments(reference.path);
+        target.query = reference.query;
+    } else {
+        target.scheme = base.scheme;
+        if reference.authority.is_some() {
+            target.authority = reference.authority;
+            path = remove_dot_segments(reference.path);
+            target.query = reference.query;
+        } else {
+            target.authority = base.authority;
+            if reference.path.is_empty() {
+                path = base.path.to_owned();
+                target.query = reference.query.or(base.query);
+            } else {
+                path = if reference.path.starts_with('/') {
+                    remove_dot_segments(reference.path)
+                } else if base.authority.is_some() && base.path.is_empty() {
+                    remove_dot_segments(&format!("/{}", reference.path))
+                } else {
+                    let base_directory = &base.path[..base.path.rfind('/').map_or(0, |i| i + 1)];
+                    remove_dot_segments(&format!("{}{}", base_directory, reference.path))
+                };
+                target.query = reference.query;
+            }
+        }
+    }
+    target.path = &path;
+    target.fragment = reference.fragment;
+
+    let iri = target.to_string();
+    validate_iri(&iri)?;
+    Ok(iri)
+}
+
+/// Converts the IRI references found in a document into absolute IRIs
+///
+/// Parsers and serializers share it to handle bases and prefixes, and custom implementations could be provided to change the resolution.
+/// The Turtle parser uses a `PrefixedIriResolver` updated by the `@base` and `@prefix` directives.
+pub trait IriResolver {
+    /// Returns the absolute IRI of the reference
+    fn resolve(&self, reference: &str) -> Result<String, IriError>;
+
+    /// Returns the IRI of the prefixed name `prefix:local`
+    ///
+    /// The prefixed names are kept apart from the IRI references, so that a prefix named like a scheme does not change the absolute IRIs.
+    /// The resolvers without prefixes return an `UnknownPrefix` error.
+    fn resolve_prefixed_name(&self, prefix: &str, _local: &str) -> Result<String, IriError> {
+        Err(IriError::UnknownPrefix(prefix.to_owned()))
+    }
+}
+
+/// Resolves the IRI references against a base IRI
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct BaseIriResolver {
+    base: String,
+}
+
+impl BaseIriResolver {
+    pub fn new(base: impl Into<String>) -> Result<Self, IriError> {
+        let base = base.into();
+        validate_iri(&base)?;
+        Ok(BaseIriResolver { base })
+    }
+}
+
+impl IriResolver for BaseIriResolver {
+    fn resolve(&self, reference: &str) -> Result<String, IriError> {
+        resolve_iri(reference, &self.base)
+    }
+}
+
+/// Expands prefixed names like `ex:foo` using a prefix map and resolves the IRI references against an optional base IRI
+///
+/// Without base IRI, the IRI references should be absolute IRIs.
+#[derive(Eq, PartialEq, Debug, Clone, Default)]
+pub struct PrefixedIriResolver {
+    base: Option<String>,
+    prefixes: HashMap<String, String>,
+}
+
+impl PrefixedIriResolver {
+    pub fn new(base: Option<String>, prefixes: HashMap<String, String>) -> Result<Self, IriError> {
+        if let Some(base) = &base {
+            validate_iri(base)?;
+        }
+        Ok(PrefixedIriResolver { base, prefixes })
+    }
+
+    /// Declares a prefix, replacing the previous declaration of the same prefix
+    pub fn add_prefix(&mut self, prefix: impl Into<String>, iri: impl Into<String>) {
+        self.prefixes.insert(prefix.into(), iri.into());
+    }
+
+    /// Replaces the base IRI, the new one being resolved against the current one if there is one
+    pub fn set_base(&mut self, base: &str) -> Result<(), IriError> {
+        self.base = Some(self.resolve(base)?);
+        Ok(())
+    }
+
+    pub fn base(&self) -> Option<&str> {
+        self.base.as_deref()
+    }
+
+    /// The declared prefixes, without their trailing `:`, and their IRIs
+    pub fn prefixes(&self) -> &HashMap<String, String> {
+        &self.prefixes
+    }
+}
+
+impl IriResolver for PrefixedIriResolver {
+    fn resolve(&self, reference: &str) -> Result<String, IriError> {
+        match &self.base {
+            Some(base) => resolve_iri(reference, base),
+            None => validate_iri(reference).map(|_| reference.to_owned()),
+        }
+    }
+
+    fn resolve_prefixed_name(&self, prefix: &str, local: &str) -> Result<String, IriError> {
+        match self.prefixes.get(prefix) {
+            Some(namespace) => Ok(format!("{}{}", namespace, local)),
+            None => Err(IriError::UnknownPrefix(prefix.to_owned())),
+        }
+    }
+}
+
+/// The components of an IRI reference as defined in [RFC 3986](https://tools.ietf.org/html/rfc3986#section-3)
+#[derive(Default)]
+struct IriComponents<'a> {
+    scheme: Option<&'a str>,
+    authority: Option<&'a str>,
+    path: &'a str,
+    query: Option<&'a str>,
+    fragment: Option<&'a str>,
+}
+
+impl<'a> IriComponents<'a> {
+    fn parse(iri: &'a str) -> Self {
+        let mut components = IriComponents::default();
+        let mut rest = iri;
+        if let Some(i) = rest.find('#') {
+            components.fragment = Some(&rest[i + 1..]);
+            rest = &rest[..i];
+        }
+        if let Some(i) = rest.find('?') {
+            components.query = Some(&rest[i + 1..]);
+            rest = &rest[..i];
+        }
+        if let Some(i) = rest.find(':') {
+            let scheme = &rest[..i];
+            let mut chars = scheme.chars();
+            if chars.next().is_some_and(|c| c.is_ascii_alphabetic())
+                && chars.all(|c| c.is_ascii_alphanumeric() || c == '+' || c == '-' || c == '.')
+            {
+                components.scheme = Some(scheme);
+                rest = &rest[i + 1..];
+            }
+        }
+        if rest.starts_with("//") {
+            let end = rest[2..].find('/').map_or(rest.len(), |i| i + 2);
+            components.authority = Some(&rest[2..end]);
+            rest = &rest[end..];
+        }
+        components.path = rest;
+        components
+    }
+}
+
+impl<'a> fmt::Display for IriComponents<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if let Some(scheme) = self.scheme {
+            write!(f, "{}:", scheme)?;
+        }
+        if let Some(authority) = self.authority {
+            write!(f, "//{}", authority)?;
+        }
+        write!(f, "{}", self.path)?;
+        if let Some(query) = self.query {
+            write!(f, "?{}", query)?;
+        }
+        if let Some(fragment) = self.fragment {
+            write!(f, "#{}", fragment)?;
+        }
+        Ok(())
+    }
+}
+
+/// Implements [RFC 3986 section 5.2.4](https://tools.ietf.org/html/rfc3986#section-5.2.4)
+fn remove_dot_segments(path: &str) -> String {
+    let mut output: Vec<&str> = Vec::default();
+    let mut input = path;
+    while !input.is_empty() {
+        if input.starts_with("../") {
+            input = &input[3..];
+        } else if input.starts_with("./") || input.starts_with("/./") {
+            input = &input[2..];
+        } else if input == "/." {
+            input = "/";
+        } else if input.starts_with("/../") || input == "/.." {
+            input = if input == "/.." { "/" } else { &input[3..] };
+            output.pop();
+        } else if input == "." || input == ".." {
+            input = "";
+        } else {
+            // We move the first segment with its leading "/" if any to the output
+            let start = if input.starts_with('/') { 1 } else { 0 };
+            let end = input[start..].find('/').map_or(input.len(), |i| i + start);
+            output.push(&input[..end]);
+            input = &input[end..];
+        }
+    }
+    output.concat()
+}
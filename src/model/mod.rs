@@ -1 +1,10 @@
 pub mod data;
+pub mod dataset;
+pub mod datatypes;
+pub mod graph;
+pub mod iri;
+pub mod pattern;
+pub mod rdfjs;
+pub mod shapes;
+pub mod vocab;
+pub mod xsd;
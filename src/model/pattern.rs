@@ -0,0 +1,114 @@
+//! Implements triple patterns with variables and the instantiation of [SPARQL CONSTRUCT](https://www.w3.org/TR/sparql11-query/#construct) templates
+use model::data::*;
+use model::graph::Graph;
+use std::collections::HashMap;
+
+/// A term or a variable in a `TriplePattern`
+#[derive(Eq, PartialEq, Debug, Clone, Hash)]
+pub enum TermPattern {
+    Term(Term),
+    /// A variable, identified by its name without the leading `?`
+    Variable(String),
+}
+
+impl TermPattern {
+    pub fn variable(name: impl Into<String>) -> Self {
+        TermPattern::Variable(name.into())
+    }
+}
+
+impl<T: Into<Term>> From<T> for TermPattern {
+    fn from(term: T) -> Self {
+        TermPattern::Term(term.into())
+    }
+}
+
+/// A triple whose terms may be variables
+#[derive(Eq, PartialEq, Debug, Clone, Hash)]
+pub struct TriplePattern {
+    subject: TermPattern,
+    predicate: TermPattern,
+    object: TermPattern,
+}
+
+impl TriplePattern {
+    pub fn new(
+        subject: impl Into<TermPattern>,
+        predicate: impl Into<TermPattern>,
+        object: impl Into<TermPattern>,
+    ) -> Self {
+        TriplePattern {
+            subject: subject.into(),
+            predicate: predicate.into(),
+            object: object.into(),
+        }
+    }
+
+    pub fn subject(&self) -> &TermPattern {
+        &self.subject
+    }
+
+    pub fn predicate(&self) -> &TermPattern {
+        &self.predicate
+    }
+
+    pub fn object(&self) -> &TermPattern {
+        &self.object
+    }
+}
+
+/// The values of the variables in a solution, indexed by variable name
+pub type Bindings = HashMap<String, Term>;
+
+/// Instantiates the template for each solution and returns the graph of the built triples
+///
+/// Like in SPARQL CONSTRUCT, the variables are replaced by their values in the solution
+/// and the blank nodes of the template are replaced by fresh blank nodes for each solution.
+/// The triples with an unbound variable, a literal subject or a predicate that is not an IRI are skipped.
+pub fn construct(
+    template: &[TriplePattern],
+    bindings: &[Bindings],
+    data_factory: &DataFactory,
+) -> Graph {
+    let mut graph = Graph::default();
+    for solution in bindings {
+        let mut blank_nodes: HashMap<&BlankNode, BlankNode> = HashMap::default();
+        for pattern in template {
+            let mut instantiate =
+                |term| instantiate(term, solution, &mut blank_nodes, data_factory);
+            let subject = match instantiate(&pattern.subject) {
+                Some(Term::NamedNode(node)) => NamedOrBlankNode::from(node),
+                Some(Term::BlankNode(node)) => NamedOrBlankNode::from(node),
+                _ => continue,
+            };
+            let predicate = match instantiate(&pattern.predicate) {
+                Some(Term::NamedNode(node)) => node,
+                _ => continue,
+            };
+            if let Some(object) = instantiate(&pattern.object) {
+                graph.insert(data_factory.triple(subject, predicate, object));
+            }
+        }
+    }
+    graph
+}
+
+/// Replaces a variable by its value and a blank node by the fresh blank node of the solution
+fn instantiate<'a>(
+    pattern: &'a TermPattern,
+    solution: &Bindings,
+    blank_nodes: &mut HashMap<&'a BlankNode, BlankNode>,
+    data_factory: &DataFactory,
+) -> Option<Term> {
+    match pattern {
+        TermPattern::Variable(name) => solution.get(name).cloned(),
+        TermPattern::Term(Term::BlankNode(node)) => Some(
+            blank_nodes
+                .entry(node)
+                .or_insert_with(|| data_factory.new_blank_node())
+                .clone()
+                .into(),
+        ),
+        TermPattern::Term(term) => Some(term.clone()),
+    }
+}
@@ -0,0 +1,128 @@
+//! Converts the terms of [RDFjs](http://rdf.js.org/data-model-spec/) plain objects into `Term`s
+use model::data::*;
+use model::vocab::xsd;
+use std::error::Error;
+use std::fmt;
+
+/// A term with the shape of the RDFjs [`Term`](http://rdf.js.org/data-model-spec/#term-interface) objects
+///
+/// It mirrors the `{termType, value, language, datatype}` objects of JavaScript libraries and could be built with its builder methods:
+/// `RdfJsTerm::literal("foo").with_language("en")`.
+#[derive(Eq, PartialEq, Debug, Clone, Hash, Default)]
+pub struct RdfJsTerm {
+    /// The RDFjs term type like `NamedNode`, `BlankNode` or `Literal`
+    pub term_type: String,
+    /// The IRI, the blank node identifier or the literal lexical form
+    pub value: String,
+    /// The literal language tag or an empty string
+    pub language: String,
+    /// The literal datatype, `xsd:string` if absent
+    pub datatype: Option<Box<RdfJsTerm>>,
+}
+
+impl RdfJsTerm {
+    pub fn named_node(iri: impl Into<String>) -> Self {
+        Self::new("NamedNode", iri)
+    }
+
+    pub fn blank_node(id: impl Into<String>) -> Self {
+        Self::new("BlankNode", id)
+    }
+
+    pub fn literal(value: impl Into<String>) -> Self {
+        Self::new("Literal", value)
+    }
+
+    /// Sets the literal language tag
+    pub fn with_language(mut self, language: impl Into<String>) -> Self {
+        self.language = language.into();
+        self
+    }
+
+    /// Sets the literal datatype
+    pub fn with_datatype(mut self, datatype: RdfJsTerm) -> Self {
+        self.datatype = Some(Box::new(datatype));
+        self
+    }
+
+    fn new(term_type: &str, value: impl Into<String>) -> Self {
+        RdfJsTerm {
+            term_type: term_type.to_owned(),
+            value: value.into(),
+            ..RdfJsTerm::default()
+        }
+    }
+}
+
+impl DataFactory {
+    /// Builds the term described by a RDFjs term
+    ///
+    /// Only the `NamedNode`, `BlankNode` and `Literal` term types are supported.
+    /// Literals with a non-empty language tag are language-tagged strings, whatever their datatype.
+    pub fn from_rdfjs(&self, term: &RdfJsTerm) -> Result<Term, RdfJsTermError> {
+        match term.term_type.as_str() {
+            "NamedNode" => Ok(self.named_node(term.value.as_str()).into()),
+            "BlankNode" => Ok(self.blank_node(term.value.as_str()).into()),
+            "Literal" => {
+                if !term.language.is_empty() {
+                    return Ok(self
+                        .language_tagged_literal(term.value.as_str(), term.language.as_str())
+                        .into());
+                }
+                match &term.datatype {
+                    None => Ok(self.simple_literal(term.value.as_str()).into()),
+                    Some(datatype) if datatype.term_type != "NamedNode" => {
+                        Err(RdfJsTermError::InvalidDatatype(datatype.term_type.clone()))
+                    }
+                    Some(datatype) if datatype.value == xsd::STRING => {
+                        Ok(self.simple_literal(term.value.as_str()).into())
+                    }
+                    Some(datatype) => Ok(self
+                        .typed_literal_checked(
+                            term.value.as_str(),
+                            self.named_node(datatype.value.as_str()),
+                        )
+                        .map_err(RdfJsTermError::InvalidLiteral)?
+                        .into()),
+                }
+            }
+            term_type => Err(RdfJsTermError::UnsupportedTermType(term_type.to_owned())),
+        }
+    }
+}
+
+/// An error raised by `DataFactory::from_rdfjs`
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum RdfJsTermError {
+    /// The term type is not `NamedNode`, `BlankNode` or `Literal`, like `Variable` or `DefaultGraph`
+    UnsupportedTermType(String),
+    /// The literal datatype has an other term type than `NamedNode`
+    InvalidDatatype(String),
+    /// The literal could not be built
+    InvalidLiteral(LiteralError),
+}
+
+impl fmt::Display for RdfJsTermError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            RdfJsTermError::UnsupportedTermType(term_type) => {
+                write!(f, "The {} term type is not supported", term_type)
+            }
+            RdfJsTermError::InvalidDatatype(term_type) => write!(
+                f,
+                "Literal datatypes should be NamedNode and not {}",
+                term_type
+            ),
+            RdfJsTermError::InvalidLiteral(error) => error.fmt(f),
+        }
+    }
+}
+
+impl Error for RdfJsTermError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            RdfJsTermError::InvalidLiteral(error) => Some(error),
+            _ => None,
+        }
+    }
+}
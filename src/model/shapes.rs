@@ -0,0 +1,41 @@
+//! Implements a lightweight validation of graphs against simple shapes inspired by [SHACL](https://www.w3.org/TR/shacl/)
+use model::data::*;
+
+/// A set of constraints on the instances of a class
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Shape {
+    /// The constraints apply to the subjects of `rdf:type` triples with this class as object
+    pub class: NamedNode,
+    pub properties: Vec<PropertyConstraint>,
+}
+
+/// A constraint on the values of a predicate for a subject
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct PropertyConstraint {
+    pub predicate: NamedNode,
+    /// The minimal number of objects the subject should have for the predicate
+    pub min_count: usize,
+    /// The maximal number of objects the subject should have for the predicate if any
+    pub max_count: Option<usize>,
+    /// The datatype all objects should be literals of if any
+    pub datatype: Option<NamedNode>,
+}
+
+/// A violation of a `PropertyConstraint`
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct Violation {
+    /// The subject violating the constraint
+    pub focus_node: NamedOrBlankNode,
+    pub predicate: NamedNode,
+    pub kind: ViolationKind,
+}
+
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum ViolationKind {
+    /// The subject has less objects than `min_count`
+    MinCount { expected: usize, actual: usize },
+    /// The subject has more objects than `max_count`
+    MaxCount { expected: usize, actual: usize },
+    /// The object is not a literal with the expected datatype
+    Datatype { expected: NamedNode, value: Term },
+}
@@ -0,0 +1,61 @@
+//! IRIs of common RDF vocabularies
+
+/// The [RDF](https://www.w3.org/TR/rdf11-schema/) vocabulary
+pub mod rdf {
+    pub const TYPE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#type";
+    pub const FIRST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#first";
+    pub const REST: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#rest";
+    pub const NIL: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#nil";
+    pub const LANG_STRING: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#langString";
+    pub const PROPERTY: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#Property";
+    pub const STATEMENT: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#Statement";
+    pub const SUBJECT: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#subject";
+    pub const PREDICATE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#predicate";
+    pub const OBJECT: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#object";
+}
+
+/// The [RDFS](https://www.w3.org/TR/rdf11-schema/) vocabulary
+pub mod rdfs {
+    pub const LABEL: &str = "http://www.w3.org/2000/01/rdf-schema#label";
+    pub const SUB_CLASS_OF: &str = "http://www.w3.org/2000/01/rdf-schema#subClassOf";
+    pub const SUB_PROPERTY_OF: &str = "http://www.w3.org/2000/01/rdf-schema#subPropertyOf";
+    pub const DOMAIN: &str = "http://www.w3.org/2000/01/rdf-schema#domain";
+    pub const RANGE: &str = "http://www.w3.org/2000/01/rdf-schema#range";
+    pub const CLASS: &str = "http://www.w3.org/2000/01/rdf-schema#Class";
+    pub const DATATYPE: &str = "http://www.w3.org/2000/01/rdf-schema#Datatype";
+}
+
+/// The [OWL](https://www.w3.org/TR/owl2-rdf-based-semantics/) vocabulary
+pub mod owl {
+    pub const CLASS: &str = "http://www.w3.org/2002/07/owl#Class";
+    pub const RESTRICTION: &str = "http://www.w3.org/2002/07/owl#Restriction";
+    pub const ONTOLOGY: &str = "http://www.w3.org/2002/07/owl#Ontology";
+    pub const OBJECT_PROPERTY: &str = "http://www.w3.org/2002/07/owl#ObjectProperty";
+    pub const DATATYPE_PROPERTY: &str = "http://www.w3.org/2002/07/owl#DatatypeProperty";
+    pub const ANNOTATION_PROPERTY: &str = "http://www.w3.org/2002/07/owl#AnnotationProperty";
+    pub const FUNCTIONAL_PROPERTY: &str = "http://www.w3.org/2002/07/owl#FunctionalProperty";
+    pub const INVERSE_FUNCTIONAL_PROPERTY: &str =
+        "http://www.w3.org/2002/07/owl#InverseFunctionalProperty";
+    pub const TRANSITIVE_PROPERTY: &str = "http://www.w3.org/2002/07/owl#TransitiveProperty";
+    pub const SYMMETRIC_PROPERTY: &str = "http://www.w3.org/2002/07/owl#SymmetricProperty";
+    pub const EQUIVALENT_CLASS: &str = "http://www.w3.org/2002/07/owl#equivalentClass";
+    pub const EQUIVALENT_PROPERTY: &str = "http://www.w3.org/2002/07/owl#equivalentProperty";
+    pub const DISJOINT_WITH: &str = "http://www.w3.org/2002/07/owl#disjointWith";
+    pub const INVERSE_OF: &str = "http://www.w3.org/2002/07/owl#inverseOf";
+    pub const ON_PROPERTY: &str = "http://www.w3.org/2002/07/owl#onProperty";
+    pub const SOME_VALUES_FROM: &str = "http://www.w3.org/2002/07/owl#someValuesFrom";
+    pub const ALL_VALUES_FROM: &str = "http://www.w3.org/2002/07/owl#allValuesFrom";
+    pub const HAS_VALUE: &str = "http://www.w3.org/2002/07/owl#hasValue";
+    pub const CARDINALITY: &str = "http://www.w3.org/2002/07/owl#cardinality";
+    pub const MIN_CARDINALITY: &str = "http://www.w3.org/2002/07/owl#minCardinality";
+    pub const MAX_CARDINALITY: &str = "http://www.w3.org/2002/07/owl#maxCardinality";
+    pub const UNION_OF: &str = "http://www.w3.org/2002/07/owl#unionOf";
+    pub const INTERSECTION_OF: &str = "http://www.w3.org/2002/07/owl#intersectionOf";
+    pub const COMPLEMENT_OF: &str = "http://www.w3.org/2002/07/owl#complementOf";
+    pub const IMPORTS: &str = "http://www.w3.org/2002/07/owl#imports";
+}
+
+/// The [XSD](https://www.w3.org/TR/xmlschema11-2/) datatypes
+pub mod xsd {
+    pub const STRING: &str = "http://www.w3.org/2001/XMLSchema#string";
+}
@@ -0,0 +1,627 @@
+//! Implements lexical forms parsing and serialization for some [XML Schema datatypes](https://www.w3.org/TR/xmlschema11-2/)
+use std::cmp::Ordering;
+use std::fmt;
+use std::time::Duration;
+
+pub(crate) const XSD_HEX_BINARY: &str = "http://www.w3.org/2001/XMLSchema#hexBinary";
+pub(crate) const XSD_BASE64_BINARY: &str = "http://www.w3.org/2001/XMLSchema#base64Binary";
+pub(crate) const XSD_BOOLEAN: &str = "http://www.w3.org/2001/XMLSchema#boolean";
+pub(crate) const XSD_DECIMAL: &str = "http://www.w3.org/2001/XMLSchema#decimal";
+pub(crate) const XSD_FLOAT: &str = "http://www.w3.org/2001/XMLSchema#float";
+pub(crate) const XSD_DOUBLE: &str = "http://www.w3.org/2001/XMLSchema#double";
+pub(crate) const XSD_INTEGER_TYPES: &[&str] = &[
+    "http://www.w3.org/2001/XMLSchema#integer",
+    "http://www.w3.org/2001/XMLSchema#nonPositiveInteger",
+    "http://www.w3.org/2001/XMLSchema#negativeInteger",
+    "http://www.w3.org/2001/XMLSchema#long",
+    "http://www.w3.org/2001/XMLSchema#int",
+    "http://www.w3.org/2001/XMLSchema#short",
+    "http://www.w3.org/2001/XMLSchema#byte",
+    "http://www.w3.org/2001/XMLSchema#nonNegativeInteger",
+    "http://www.w3.org/2001/XMLSchema#unsignedLong",
+    "http://www.w3.org/2001/XMLSchema#unsignedInt",
+    "http://www.w3.org/2001/XMLSchema#unsignedShort",
+    "http://www.w3.org/2001/XMLSchema#unsignedByte",
+    "http://www.w3.org/2001/XMLSchema#positiveInteger",
+];
+pub(crate) const XSD_DATE_TIME: &str = "http://www.w3.org/2001/XMLSchema#dateTime";
+pub(crate) const XSD_DATE_TIME_STAMP: &str = "http://www.w3.org/2001/XMLSchema#dateTimeStamp";
+pub(crate) const XSD_DURATION: &str = "http://www.w3.org/2001/XMLSchema#duration";
+pub(crate) const XSD_DAY_TIME_DURATION: &str = "http://www.w3.org/2001/XMLSchema#dayTimeDuration";
+pub(crate) const XSD_YEAR_MONTH_DURATION: &str =
+    "http://www.w3.org/2001/XMLSchema#yearMonthDuration";
+
+/// The encodings of binary data in literals
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Hash)]
+pub enum BinaryEncoding {
+    /// [xsd:hexBinary](https://www.w3.org/TR/xmlschema11-2/#hexBinary)
+    Hex,
+    /// [xsd:base64Binary](https://www.w3.org/TR/xmlschema11-2/#base64Binary)
+    Base64,
+}
+
+impl BinaryEncoding {
+    /// The IRI of the datatype using this encoding
+    pub fn datatype(self) -> &'static str {
+        match self {
+            BinaryEncoding::Hex => XSD_HEX_BINARY,
+            BinaryEncoding::Base64 => XSD_BASE64_BINARY,
+        }
+    }
+
+    /// Builds the canonical lexical form of the bytes
+    pub fn encode(self, bytes: &[u8]) -> String {
+        match self {
+            BinaryEncoding::Hex => encode_hex(bytes),
+            BinaryEncoding::Base64 => encode_base64(bytes),
+        }
+    }
+
+    /// Parses a lexical form and returns None if it is not valid
+    pub fn decode(self, value: &str) -> Option<Vec<u8>> {
+        match self {
+            BinaryEncoding::Hex => decode_hex(value),
+            BinaryEncoding::Base64 => decode_base64(value),
+        }
+    }
+}
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789ABCDEF";
+
+fn encode_hex(bytes: &[u8]) -> String {
+    let mut value = String::with_capacity(2 * bytes.len());
+    for byte in bytes {
+        value.push(char::from(HEX_DIGITS[usize::from(byte >> 4)]));
+        value.push(char::from(HEX_DIGITS[usize::from(byte & 0xF)]));
+    }
+    value
+}
+
+fn decode_hex(value: &str) -> Option<Vec<u8>> {
+    if !value.len().is_multiple_of(2) {
+        return None;
+    }
+    value
+        .as_bytes()
+        .chunks(2)
+        .map(|pair| Some((hex_digit_value(pair[0])? << 4) | hex_digit_value(pair[1])?))
+        .collect()
+}
+
+fn hex_digit_value(digit: u8) -> Option<u8> {
+    match digit {
+        b'0'..=b'9' => Some(digit - b'0'),
+        b'a'..=b'f' => Some(digit - b'a' + 10),
+        b'A'..=b'F' => Some(digit - b'A' + 10),
+        _ => None,
+    }
+}
+
+const BASE64_DIGITS: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+fn encode_base64(bytes: &[u8]) -> String {
+    let mut value = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let group = chunk.iter().enumerate().fold(0u32, |group, (i, byte)| {
+            group | u32::from(*byte) << (16 - 8 * i)
+        });
+        for i in 0..4 {
+            if i <= chunk.len() {
+                value.push(char::from(
+                    BASE64_DIGITS[((group >> (18 - 6 * i)) & 0x3F) as usize],
+                ));
+            } else {
+                value.push('=');
+            }
+        }
+    }
+    value
+}
+
+fn decode_base64(value: &str) -> Option<Vec<u8>> {
+    // The lexical space allows spaces between the characters
+    let digits: Vec<u8> = value.bytes().filter(|c| *c != b' ').collect();
+    if !digits.len().is_multiple_of(4) {
+        return None;
+    }
+    let mut bytes = Vec::with_capacity(digits.len() / 4 * 3);
+    for (i, chunk) in digits.chunks(4).enumerate() {
+        let padding = chunk.iter().rev().take_while(|c| **c == b'=').count();
+        if padding > 2 || (padding > 0 && (i + 1) * 4 != digits.len()) {
+            return None;
+        }
+        let mut group = 0u32;
+        for digit in &chunk[..4 - padding] {
+            group = group << 6 | u32::from(base64_digit_value(*digit)?);
+        }
+        group <<= 6 * padding;
+        bytes.extend_from_slice(
+            &[(group >> 16) as u8, (group >> 8) as u8, group as u8][..3 - padding],
+        );
+    }
+    Some(bytes)
+}
+
+fn base64_digit_value(digit: u8) -> Option<u8> {
+    match digit {
+        b'A'..=b'Z' => Some(digit - b'A'),
+        b'a'..=b'z' => Some(digit - b'a' + 26),
+        b'0'..=b'9' => Some(digit - b'0' + 52),
+        b'+' => Some(62),
+        b'/' => Some(63),
+        _ => None,
+    }
+}
+
+/// The numeric datatypes of literals
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Hash)]
+pub enum NumericType {
+    /// [xsd:integer](https://www.w3.org/TR/xmlschema11-2/#integer) and its subtypes like xsd:int or xsd:nonNegativeInteger
+    Integer,
+    /// [xsd:decimal](https://www.w3.org/TR/xmlschema11-2/#decimal)
+    Decimal,
+    /// [xsd:float](https://www.w3.org/TR/xmlschema11-2/#float), a 32 bits floating point number
+    Float,
+    /// [xsd:double](https://www.w3.org/TR/xmlschema11-2/#double), a 64 bits floating point number
+    Double,
+}
+
+impl NumericType {
+    /// Returns the numeric type of a datatype IRI or None if it is not numeric
+    pub fn from_datatype(datatype: &str) -> Option<Self> {
+        match datatype {
+            XSD_DECIMAL => Some(NumericType::Decimal),
+            XSD_FLOAT => Some(NumericType::Float),
+            XSD_DOUBLE => Some(NumericType::Double),
+            datatype if XSD_INTEGER_TYPES.contains(&datatype) => Some(NumericType::Integer),
+            _ => None,
+        }
+    }
+
+    /// Parses a lexical form of this type into a `f64` and returns None if it is not valid
+    pub(crate) fn parse_f64(self, value: &str) -> Option<f64> {
+        match self {
+            NumericType::Integer => canonicalize_integer(value)?.parse().ok(),
+            NumericType::Decimal => canonicalize_decimal(value)?.parse().ok(),
+            NumericType::Float | NumericType::Double => match value {
+                "INF" | "+INF" => Some(f64::INFINITY),
+                "-INF" => Some(f64::NEG_INFINITY),
+                "NaN" => Some(f64::NAN),
+                // Rust also parses "inf" or "nan" that are not valid lexical forms
+                value
+                    if value.bytes().all(|c| {
+                        c.is_ascii_digit() || matches!(c, b'+' | b'-' | b'.' | b'e' | b'E')
+                    }) =>
+                {
+                    let value: f64 = value.parse().ok()?;
+                    Some(if self == NumericType::Float {
+                        f64::from(value as f32)
+                    } else {
+                        value
+                    })
+                }
+                _ => None,
+            },
+        }
+    }
+}
+
+/// Builds the [canonical representation](https://www.w3.org/TR/xmlschema11-2/#f-booleanCanmap) of a xsd:boolean lexical form
+///
+/// Returns None if the lexical form is invalid.
+pub(crate) fn canonicalize_boolean(value: &str) -> Option<String> {
+    match value {
+        "true" | "1" => Some("true".to_owned()),
+        "false" | "0" => Some("false".to_owned()),
+        _ => None,
+    }
+}
+
+/// Builds the [canonical representation](https://www.w3.org/TR/xmlschema11-2/#f-decimalCanRep) of a xsd:decimal lexical form
+///
+/// Returns None if the lexical form is invalid.
+pub(crate) fn canonicalize_decimal(value: &str) -> Option<String> {
+    let (negative, value) = split_sign(value);
+    let (integer, fraction) = match value.find('.') {
+        Some(i) => (&value[..i], &value[i + 1..]),
+        None => (value, ""),
+    };
+    if (integer.is_empty() && fraction.is_empty())
+        || !integer
+            .bytes()
+            .chain(fraction.bytes())
+            .all(|c| c.is_ascii_digit())
+    {
+        return None;
+    }
+    let integer = integer.trim_start_matches('0');
+    let fraction = fraction.trim_end_matches('0');
+    let integer = if integer.is_empty() { "0" } else { integer };
+    let is_zero = integer == "0" && fraction.is_empty();
+    let sign = if negative && !is_zero { "-" } else { "" };
+    Some(if fraction.is_empty() {
+        format!("{}{}", sign, integer)
+    } else {
+        format!("{}{}.{}", sign, integer, fraction)
+    })
+}
+
+/// Builds the [canonical representation](https://www.w3.org/TR/xmlschema11-2/#f-integerCanRep) of a xsd:integer lexical form
+///
+/// Returns None if the lexical form is invalid.
+pub(crate) fn canonicalize_integer(value: &str) -> Option<String> {
+    let (_, digits) = split_sign(value);
+    if digits.is_empty() || !digits.bytes().all(|c| c.is_ascii_digit()) {
+        return None;
+    }
+    canonicalize_decimal(value)
+}
+
+/// Splits the optional leading sign of a number and returns if it is negative
+fn split_sign(value: &str) -> (bool, &str) {
+    if let Some(value) = value.strip_prefix('-') {
+        (true, value)
+    } else {
+        (false, value.strip_prefix('+').unwrap_or(value))
+    }
+}
+
+/// Builds the canonical representation of a xsd:dateTime lexical form, keeping its timezone offset
+///
+/// Returns None if the lexical form is invalid.
+pub(crate) fn canonicalize_date_time(value: &str) -> Option<String> {
+    XsdDateTime::parse(value).map(|value| value.to_string())
+}
+
+/// A [xsd:dateTime](https://www.w3.org/TR/xmlschema11-2/#dateTime) value
+///
+/// The values are compared as instants, so `2020-01-01T12:00:00Z` is equal to `2020-01-01T14:00:00+02:00`.
+/// The values without timezone are only comparable with each other: comparing a value with a timezone and a value without one returns None.
+/// The fractional seconds are kept up to the nanosecond.
+///
+/// It is displayed in its canonical form with its timezone offset, like `2020-01-01T14:00:00+02:00`.
+#[derive(Debug, Clone, Copy)]
+pub struct XsdDateTime {
+    /// Seconds since 1970-01-01T00:00:00, in UTC if there is a timezone
+    seconds: i128,
+    nanoseconds: u32,
+    /// The timezone offset in minutes
+    timezone_offset: Option<i16>,
+}
+
+impl XsdDateTime {
+    /// Parses a [xsd:dateTime lexical form](https://www.w3.org/TR/xmlschema11-2/#nt-dateTimeRep) like `2020-01-01T12:00:00Z` or `2020-01-01T14:00:00.5+02:00`
+    pub fn parse(value: &str) -> Option<Self> {
+        let (negative, value) = match value.strip_prefix('-') {
+            Some(value) => (true, value),
+            None => (false, value),
+        };
+        let year_end = value.find('-')?;
+        let year_digits = &value[..year_end];
+        if year_digits.len() < 4
+            || (year_digits.len() > 4 && year_digits.starts_with('0'))
+            || !year_digits.bytes().all(|c| c.is_ascii_digit())
+        {
+            return None;
+        }
+        let year: i64 = year_digits.parse().ok()?;
+        if negative && year == 0 {
+            return None;
+        }
+        let year = if negative { -year } else { year };
+
+        let value = &value[year_end + 1..];
+        let bytes = value.as_bytes();
+        if bytes.len() < 14
+            || bytes[2] != b'-'
+            || bytes[5] != b'T'
+            || bytes[8] != b':'
+            || bytes[11] != b':'
+        {
+            return None;
+        }
+        let month = parse_two_digits(value.get(0..2)?)?;
+        let day = parse_two_digits(value.get(3..5)?)?;
+        let hour = parse_two_digits(value.get(6..8)?)?;
+        let minute = parse_two_digits(value.get(9..11)?)?;
+        let second = parse_two_digits(value.get(12..14)?)?;
+        let mut rest = value.get(14..)?;
+
+        let mut nanoseconds = 0;
+        if let Some(fraction) = rest.strip_prefix('.') {
+            let end = fraction
+                .find(|c: char| !c.is_ascii_digit())
+                .unwrap_or(fraction.len());
+            if end == 0 {
+                return None;
+            }
+            // We only keep nanoseconds precision
+            nanoseconds = format!("{:0<9}", &fraction[..end.min(9)]).parse().ok()?;
+            rest = &fraction[end..];
+        }
+        let timezone_offset = match rest {
+            "" => None,
+            "Z" => Some(0),
+            _ => {
+                let sign = match rest.as_bytes()[0] {
+                    b'+' => 1,
+                    b'-' => -1,
+                    _ => return None,
+                };
+                if rest.len() != 6 || rest.as_bytes()[3] != b':' {
+                    return None;
+                }
+                let hours = parse_two_digits(rest.get(1..3)?)?;
+                let minutes = parse_two_digits(rest.get(4..6)?)?;
+                if minutes > 59 || hours > 14 || (hours == 14 && minutes != 0) {
+                    return None;
+                }
+                Some(sign * (i16::from(hours) * 60 + i16::from(minutes)))
+            }
+        };
+
+        if month == 0
+            || month > 12
+            || day == 0
+            || day > days_in_month(year, month)
+            || minute > 59
+            || second > 59
+            || hour > 24
+            || (hour == 24 && (minute != 0 || second != 0 || nanoseconds != 0))
+        {
+            return None;
+        }
+        let seconds = days_from_civil(year, month, day) * 86400
+            + i128::from(hour) * 3600
+            + i128::from(minute) * 60
+            + i128::from(second)
+            - i128::from(timezone_offset.unwrap_or(0)) * 60;
+        Some(XsdDateTime {
+            seconds,
+            nanoseconds,
+            timezone_offset,
+        })
+    }
+
+    /// The timezone offset in minutes or None if the value has no timezone
+    pub fn timezone_offset(&self) -> Option<i16> {
+        self.timezone_offset
+    }
+}
+
+impl PartialEq for XsdDateTime {
+    fn eq(&self, other: &Self) -> bool {
+        self.partial_cmp(other) == Some(Ordering::Equal)
+    }
+}
+
+impl PartialOrd for XsdDateTime {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        if self.timezone_offset.is_some() != other.timezone_offset.is_some() {
+            return None;
+        }
+        Some((self.seconds, self.nanoseconds).cmp(&(other.seconds, other.nanoseconds)))
+    }
+}
+
+impl fmt::Display for XsdDateTime {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // The date and time are written in the value timezone
+        let local_seconds = self.seconds + i128::from(self.timezone_offset.unwrap_or(0)) * 60;
+        let (year, month, day) = civil_from_days(local_seconds.div_euclid(86400));
+        let seconds = local_seconds.rem_euclid(86400);
+        if year < 0 {
+            write!(f, "-{:04}", -year)?;
+        } else {
+            write!(f, "{:04}", year)?;
+        }
+        write!(
+            f,
+            "-{:02}-{:02}T{:02}:{:02}:{:02}",
+            month,
+            day,
+            seconds / 3600,
+            seconds % 3600 / 60,
+            seconds % 60
+        )?;
+        if self.nanoseconds != 0 {
+            let fraction = format!("{:09}", self.nanoseconds);
+            write!(f, ".{}", fraction.trim_end_matches('0'))?;
+        }
+        match self.timezone_offset {
+            None => Ok(()),
+            Some(0) => write!(f, "Z"),
+            Some(offset) => write!(
+                f,
+                "{}{:02}:{:02}",
+                if offset < 0 { '-' } else { '+' },
+                offset.abs() / 60,
+                offset.abs() % 60
+            ),
+        }
+    }
+}
+
+fn parse_two_digits(value: &str) -> Option<u8> {
+    if value.len() == 2 && value.bytes().all(|c| c.is_ascii_digit()) {
+        value.parse().ok()
+    } else {
+        None
+    }
+}
+
+fn days_in_month(year: i64, month: u8) -> u8 {
+    match month {
+        2 if year.rem_euclid(4) == 0
+            && (year.rem_euclid(100) != 0 || year.rem_euclid(400) == 0) =>
+        {
+            29
+        }
+        2 => 28,
+        4 | 6 | 9 | 11 => 30,
+        _ => 31,
+    }
+}
+
+/// The number of days since 1970-01-01 in the proleptic Gregorian calendar
+///
+/// See [Howard Hinnant's algorithms](https://howardhinnant.github.io/date_algorithms.html#days_from_civil).
+/// The computation is done on `i128` so that it does not overflow for any `i64` year.
+fn days_from_civil(year: i64, month: u8, day: u8) -> i128 {
+    let (year, month, day) = (i128::from(year), i128::from(month), i128::from(day));
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let year_of_era = year - era * 400;
+    let day_of_year = (153 * ((month + 9) % 12) + 2) / 5 + day - 1;
+    let day_of_era = year_of_era * 365 + year_of_era / 4 - year_of_era / 100 + day_of_year;
+    era * 146_097 + day_of_era - 719_468
+}
+
+/// The inverse of `days_from_civil`
+fn civil_from_days(days: i128) -> (i128, i128, i128) {
+    let days = days + 719_468;
+    let era = days.div_euclid(146_097);
+    let day_of_era = days - era * 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1460 + day_of_era / 36524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let shifted_month = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * shifted_month + 2) / 5 + 1;
+    let month = if shifted_month < 10 {
+        shifted_month + 3
+    } else {
+        shifted_month - 9
+    };
+    let year = year_of_era + era * 400;
+    (if month <= 2 { year + 1 } else { year }, month, day)
+}
+
+/// A [xsd:duration](https://www.w3.org/TR/xmlschema11-2/#duration) value
+///
+/// Years and months can not be converted into a fixed number of seconds so they are kept separately from the other components.
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Hash, Default)]
+pub struct XsdDuration {
+    negative: bool,
+    months: u64,
+    day_time: Duration,
+}
+
+impl XsdDuration {
+    /// Parses a [xsd:duration lexical form](https://www.w3.org/TR/xmlschema11-2/#nt-durationRep) like `PT1H30M` or `-P1Y2M`
+    pub fn parse(value: &str) -> Option<Self> {
+        let (negative, value) = match value.strip_prefix('-') {
+            Some(value) => (true, value),
+            None => (false, value),
+        };
+        let value = value.strip_prefix('P')?;
+        let (date, time) = match value.find('T') {
+            Some(i) => (&value[..i], Some(&value[i + 1..])),
+            None => (value, None),
+        };
+        let date_components = parse_duration_components(date, &['Y', 'M', 'D'])?;
+        let time_components = match time {
+            Some(time) => {
+                let components = parse_duration_components(time, &['H', 'M', 'S'])?;
+                if components.is_empty() {
+                    return None; // "T" should be followed by at least one component
+                }
+                components
+            }
+            None => Vec::default(),
+        };
+        if date_components.is_empty() && time_components.is_empty() {
+            return None;
+        }
+
+        let mut months = 0u64;
+        let mut seconds = 0u64;
+        let mut nanoseconds = 0u32;
+        for (designator, number) in date_components {
+            let number: u64 = number.parse().ok()?;
+            match designator {
+                'Y' => months = months.checked_add(number.checked_mul(12)?)?,
+                'M' => months = months.checked_add(number)?,
+                _ => seconds = seconds.checked_add(number.checked_mul(86400)?)?,
+            }
+        }
+        for (designator, number) in time_components {
+            match designator {
+                'H' => {
+                    seconds = seconds.checked_add(number.parse::<u64>().ok()?.checked_mul(3600)?)?
+                }
+                'M' => {
+                    seconds = seconds.checked_add(number.parse::<u64>().ok()?.checked_mul(60)?)?
+                }
+                _ => {
+                    let (integer, fraction) = match number.find('.') {
+                        Some(i) => (&number[..i], &number[i + 1..]),
+                        None => (number, ""),
+                    };
+                    if integer.is_empty() && fraction.is_empty() {
+                        return None;
+                    }
+                    if !integer.is_empty() {
+                        seconds = seconds.checked_add(integer.parse().ok()?)?;
+                    }
+                    // We only keep nanoseconds precision
+                    let fraction = format!("{:0<9}", &fraction[..fraction.len().min(9)]);
+                    nanoseconds = fraction.parse().ok()?;
+                }
+            }
+        }
+        Some(XsdDuration {
+            negative,
+            months,
+            day_time: Duration::new(seconds, nanoseconds),
+        })
+    }
+
+    pub fn is_negative(&self) -> bool {
+        self.negative
+    }
+
+    /// The absolute number of months of the years and months components
+    pub fn months(&self) -> u64 {
+        self.months
+    }
+
+    /// The absolute duration of the days, hours, minutes and seconds components
+    pub fn day_time(&self) -> Duration {
+        self.day_time
+    }
+
+    /// Converts the duration into a `std::time::Duration` if it is positive and has no years or months components
+    pub fn to_std_duration(&self) -> Option<Duration> {
+        if self.months == 0 && (!self.negative || self.day_time == Duration::default()) {
+            Some(self.day_time)
+        } else {
+            None
+        }
+    }
+}
+
+/// Splits a duration part like `1Y2M` into its components, checking that the designators are in the expected order
+fn parse_duration_components<'a>(
+    mut value: &'a str,
+    designators: &[char],
+) -> Option<Vec<(char, &'a str)>> {
+    let mut components = Vec::default();
+    let mut remaining_designators = designators;
+    while !value.is_empty() {
+        let end = value.find(|c: char| !c.is_ascii_digit() && c != '.')?;
+        let designator = value[end..].chars().next()?;
+        let position = remaining_designators
+            .iter()
+            .position(|d| *d == designator)?;
+        let number = &value[..end];
+        // Only seconds could have a decimal part
+        if number.is_empty() || (number.contains('.') && designator != 'S') {
+            return None;
+        }
+        components.push((designator, number));
+        remaining_designators = &remaining_designators[position + 1..];
+        value = &value[end + 1..];
+    }
+    Some(components)
+}
@@ -1,9 +1,278 @@
+use model::data::BlankNode;
+use model::data::DataFactory;
+use model::data::NamedOrBlankNode;
+use model::data::Term;
+use model::data::Triple;
+use model::data::TripleLike;
+use model::dataset::Dataset;
+use rio::ntriples::read_ntriples;
+use rio::turtle::read_turtle;
+use std::cmp;
+use std::collections::HashMap;
+use std::collections::HashSet;
 use std::error::Error;
 use std::fmt;
+use std::fs;
+use std::fs::File;
+use std::io;
+use std::iter;
+use std::iter::Peekable;
+use std::path::Path;
+use std::path::PathBuf;
 
 pub mod ntriples;
+pub mod rdfxml;
 pub mod turtle;
 
+/// The default size of the buffers used by the readers, 64 KiB
+pub const DEFAULT_BUFFER_SIZE: usize = 64 * 1024;
+
+/// Filters the triples already returned by the iterator
+///
+/// All the returned triples are kept in memory in order to detect duplicates, so the memory usage grows with the number of distinct triples.
+pub fn dedup(iter: impl Iterator<Item = Triple>) -> impl Iterator<Item = Triple> {
+    let mut seen = HashSet::new();
+    iter.filter(move |triple| seen.insert(triple.clone()))
+}
+
+/// An iterator wrapper counting the items returned so far
+///
+/// It allows to report the progress of a streaming parser whose total number of triples is not known:
+/// `Counting::new(read_ntriples(file, &data_factory))`.
+#[derive(Debug, Clone)]
+pub struct Counting<I> {
+    inner: I,
+    count: usize,
+}
+
+impl<I> Counting<I> {
+    pub fn new(inner: I) -> Self {
+        Counting { inner, count: 0 }
+    }
+
+    /// The number of items returned so far
+    pub fn count_so_far(&self) -> usize {
+        self.count
+    }
+
+    pub fn into_inner(self) -> I {
+        self.inner
+    }
+}
+
+impl<I: Iterator> Iterator for Counting<I> {
+    type Item = I::Item;
+
+    fn next(&mut self) -> Option<I::Item> {
+        let item = self.inner.next()?;
+        self.count += 1;
+        Some(item)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.inner.size_hint()
+    }
+}
+
+/// Groups by subject the triples of two iterators sorted by subject, like a sort-merge join
+///
+/// For each subject of one of the iterators, the subject is returned with its triples from the left and the right iterators,
+/// one of them being empty if the subject only appears in the other iterator.
+/// The subjects are returned in increasing order and only the triples of the current subject are kept in memory.
+///
+/// The iterators should be sorted by subject, for example like `Graph::iter_sorted` or a sorted N-Triples file.
+/// If they are not, a subject may be returned multiple times.
+pub fn join_by_subject(
+    left: impl IntoIterator<Item = Triple>,
+    right: impl IntoIterator<Item = Triple>,
+) -> impl Iterator<Item = (NamedOrBlankNode, Vec<Triple>, Vec<Triple>)> {
+    let mut left = left.into_iter().peekable();
+    let mut right = right.into_iter().peekable();
+    iter::from_fn(move || {
+        let subject = match (left.peek(), right.peek()) {
+            (Some(l), Some(r)) => cmp::min(l.subject(), r.subject()).clone(),
+            (Some(l), None) => l.subject().clone(),
+            (None, Some(r)) => r.subject().clone(),
+            (None, None) => return None,
+        };
+        let left_triples = next_subject_triples(&mut left, &subject);
+        let right_triples = next_subject_triples(&mut right, &subject);
+        Some((subject, left_triples, right_triples))
+    })
+}
+
+/// Consumes the triples with the given subject at the beginning of the iterator
+fn next_subject_triples(
+    iter: &mut Peekable<impl Iterator<Item = Triple>>,
+    subject: &NamedOrBlankNode,
+) -> Vec<Triple> {
+    let mut triples = Vec::default();
+    while let Some(triple) = iter.next_if(|triple| triple.subject() == subject) {
+        triples.push(triple);
+    }
+    triples
+}
+
+/// A consumer of triples, like a serializer
+///
+/// It is object safe so custom outputs could be plugged using `&mut dyn TripleSink`.
+pub trait TripleSink {
+    /// Consumes a triple
+    fn push(&mut self, triple: &Triple) -> RioResult<()>;
+
+    /// Notifies the sink that there are no more triples to consume
+    fn finish(&mut self) -> RioResult<()> {
+        Ok(())
+    }
+}
+
+/// Loads the RDF files of a directory, each file being loaded in a named graph
+///
+/// The graph names are the `file:` IRIs of the files absolute paths.
+/// The format is guessed from the file extension: `.ttl` for Turtle and `.nt` for N-Triples.
+/// The files with an other extension and the subdirectories are ignored.
+/// The blank nodes are scoped to their file: the blank nodes of the dataset are new blank nodes of `data_factory`,
+/// so two files using the same blank node label do not share a blank node.
+pub fn load_dataset_dir(dir: &Path, data_factory: &DataFactory) -> Result<Dataset, LoadError> {
+    let io_error = |path: &Path| {
+        let path = path.to_owned();
+        move |error| LoadError::Io { path, error }
+    };
+    let mut dataset = Dataset::default();
+    for entry in fs::read_dir(dir).map_err(io_error(dir))? {
+        let path = entry.map_err(io_error(dir))?.path();
+        let format = match path.extension().and_then(|extension| extension.to_str()) {
+            Some("ttl") => FileFormat::Turtle,
+            Some("nt") => FileFormat::NTriples,
+            _ => continue,
+        };
+        if !path.is_file() {
+            continue;
+        }
+        let graph_name: Option<NamedOrBlankNode> = Some(
+            data_factory
+                .named_node(file_iri(&path.canonicalize().map_err(io_error(&path))?))
+                .into(),
+        );
+        let mut blank_nodes = HashMap::default();
+        let file = File::open(&path).map_err(io_error(&path))?;
+        let syntax_error = |error| LoadError::Syntax {
+            path: path.clone(),
+            error,
+        };
+        match format {
+            FileFormat::Turtle => {
+                for triple in read_turtle(file, data_factory).map_err(syntax_error)? {
+                    dataset.insert(
+                        with_fresh_blank_nodes(triple, data_factory, &mut blank_nodes)
+                            .in_graph(&graph_name),
+                    );
+                }
+            }
+            FileFormat::NTriples => {
+                for triple in read_ntriples(file, data_factory) {
+                    let triple = triple.map_err(syntax_error)?;
+                    dataset.insert(
+                        with_fresh_blank_nodes(triple, data_factory, &mut blank_nodes)
+                            .in_graph(&graph_name),
+                    );
+                }
+            }
+        }
+    }
+    Ok(dataset)
+}
+
+/// Replaces the blank nodes of the triple by the fresh blank nodes they are mapped to, creating the missing ones
+fn with_fresh_blank_nodes(
+    triple: Triple,
+    data_factory: &DataFactory,
+    blank_nodes: &mut HashMap<BlankNode, BlankNode>,
+) -> Triple {
+    let mut fresh = |node: &BlankNode| {
+        blank_nodes
+            .entry(node.clone())
+            .or_insert_with(|| data_factory.new_blank_node())
+            .clone()
+    };
+    let subject: NamedOrBlankNode = match triple.subject() {
+        NamedOrBlankNode::BlankNode(node) => fresh(node).into(),
+        subject => subject.clone(),
+    };
+    let object: Term = match triple.object() {
+        Term::BlankNode(node) => fresh(node).into(),
+        object => object.clone(),
+    };
+    data_factory.triple(subject, triple.predicate().clone(), object)
+}
+
+enum FileFormat {
+    Turtle,
+    NTriples,
+}
+
+/// Builds the `file:` IRI of an absolute path, percent-encoding the characters that are not allowed in IRI paths
+fn file_iri(path: &Path) -> String {
+    let mut iri = String::from("file://");
+    let path = path.to_string_lossy();
+    if !path.starts_with('/') {
+        iri.push('/');
+    }
+    for c in path.chars() {
+        match c {
+            '\\' => iri.push('/'),
+            'a'..='z' | 'A'..='Z' | '0'..='9' | '-' | '.' | '_' | '~' | '/' | ':' => iri.push(c),
+            c => {
+                let mut buffer = [0; 4];
+                for byte in c.encode_utf8(&mut buffer).bytes() {
+                    iri.push_str(&format!("%{:02X}", byte));
+                }
+            }
+        }
+    }
+    iri
+}
+
+/// An error raised by `load_dataset_dir`
+#[derive(Debug)]
+pub enum LoadError {
+    /// A file or the directory could not be read
+    Io { path: PathBuf, error: io::Error },
+    /// A file is not valid in the format guessed from its extension
+    Syntax { path: PathBuf, error: RioError },
+}
+
+impl LoadError {
+    /// The path of the file or directory that failed to be loaded
+    pub fn path(&self) -> &Path {
+        match self {
+            LoadError::Io { path, .. } | LoadError::Syntax { path, .. } => path,
+        }
+    }
+}
+
+impl fmt::Display for LoadError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            LoadError::Io { path, error } => {
+                write!(f, "Failed to read {}: {}", path.display(), error)
+            }
+            LoadError::Syntax { path, error } => {
+                write!(f, "Failed to parse {}: {}", path.display(), error)
+            }
+        }
+    }
+}
+
+impl Error for LoadError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            LoadError::Io { error, .. } => Some(error),
+            LoadError::Syntax { error, .. } => Some(error),
+        }
+    }
+}
+
 pub type RioResult<T> = Result<T, RioError>;
 
 #[derive(Debug)]
@@ -20,6 +289,11 @@ impl RioError {
             error: error.into(),
         }
     }
+
+    /// Returns the underlying error if it has the type `E`
+    pub fn downcast_ref<E: Error + 'static>(&self) -> Option<&E> {
+        self.error.downcast_ref()
+    }
 }
 
 impl fmt::Display for RioError {
@@ -29,11 +303,13 @@ impl fmt::Display for RioError {
 }
 
 impl Error for RioError {
-    fn description(&self) -> &str {
-        self.error.description()
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        Some(&*self.error)
     }
+}
 
-    fn cause(&self) -> Option<&Error> {
-        Some(&*self.error)
+impl From<io::Error> for RioError {
+    fn from(error: io::Error) -> Self {
+        RioError::new(error)
     }
 }
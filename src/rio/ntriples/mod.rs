@@ -6,17 +6,59 @@ mod grammar {
 
 use model::data::*;
 use rio::*;
+use std::fmt;
+use std::io;
 use std::io::BufRead;
 use std::io::BufReader;
 use std::io::Read;
+use std::io::Write;
+use std::thread;
 
 pub fn read_ntriples<'a, R: Read + 'a>(
     source: R,
     data_factory: &'a DataFactory,
+) -> impl Iterator<Item = RioResult<Triple>> {
+    read_ntriples_with_options(source, data_factory, &NTriplesOptions::default())
+}
+
+/// Options of the N-Triples parser
+///
+/// They are built from the default options with the `with_` methods, like `NTriplesOptions::default().with_buffer_size(4096)`.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct NTriplesOptions {
+    /// The size of the buffer used to read the document, `DEFAULT_BUFFER_SIZE` by default.
+    /// A size of 0 is read as 1.
+    pub buffer_size: usize,
+}
+
+impl Default for NTriplesOptions {
+    fn default() -> Self {
+        NTriplesOptions {
+            buffer_size: DEFAULT_BUFFER_SIZE,
+        }
+    }
+}
+
+impl NTriplesOptions {
+    /// Sets the size of the buffer used to read the document
+    pub fn with_buffer_size(mut self, buffer_size: usize) -> Self {
+        self.buffer_size = buffer_size;
+        self
+    }
+}
+
+/// Reads a N-Triples document like `read_ntriples` using the given parser options
+pub fn read_ntriples_with_options<'a, R: Read + 'a>(
+    source: R,
+    data_factory: &'a DataFactory,
+    options: &NTriplesOptions,
 ) -> impl Iterator<Item = RioResult<Triple>> {
     let factory = data_factory.clone(); //TODO: try to avoid clone here
                                         //TODO: use read_lines to avoid allocations
-    BufReader::new(source)
+
+    // An empty buffer would be read as the end of the document
+    BufReader::with_capacity(options.buffer_size.max(1), source)
         .lines()
         .flat_map(move |line| match line {
             Ok(line) => match grammar::triple(line.as_str(), &factory) {
@@ -26,3 +68,345 @@ pub fn read_ntriples<'a, R: Read + 'a>(
             Err(error) => Some(Err(RioError::new(error))),
         })
 }
+
+/// Reads a N-Triples document like `read_ntriples`, parsing its lines on all the available threads
+///
+/// The document is fully loaded in memory. The triples are returned in the document order.
+/// Blank node labels are used as blank node ids so the same label gives the same blank node in all the threads.
+pub fn read_ntriples_parallel<R: Read>(
+    source: R,
+    data_factory: &DataFactory,
+) -> RioResult<Vec<Triple>> {
+    let mut text = String::default();
+    BufReader::new(source).read_to_string(&mut text)?;
+    let lines: Vec<&str> = text.lines().collect();
+    let threads = thread::available_parallelism().map_or(1, |threads| threads.get());
+    let chunk_size = lines.len().div_ceil(threads).max(1);
+    let chunks: Vec<RioResult<Vec<Triple>>> = thread::scope(|scope| {
+        let handles: Vec<_> = lines
+            .chunks(chunk_size)
+            .map(|chunk| scope.spawn(move || parse_lines(chunk, data_factory)))
+            .collect();
+        handles
+            .into_iter()
+            .map(|handle| handle.join().expect("A N-Triples parsing thread panicked"))
+            .collect()
+    });
+    let mut triples = Vec::with_capacity(lines.len());
+    for chunk in chunks {
+        triples.extend(chunk?);
+    }
+    Ok(triples)
+}
+
+fn parse_lines(lines: &[&str], data_factory: &DataFactory) -> RioResult<Vec<Triple>> {
+    let mut triples = Vec::with_capacity(lines.len());
+    for line in lines {
+        if let Some(triple) = grammar::triple(line, data_factory).map_err(RioError::new)? {
+            triples.push(triple);
+        }
+    }
+    Ok(triples)
+}
+
+/// Writes the triples in N-Triples
+pub fn write_ntriples<'a, W: Write>(
+    triples: impl IntoIterator<Item = &'a Triple>,
+    sink: W,
+) -> RioResult<()> {
+    let mut sink = NTriplesSink::new(sink);
+    for triple in triples {
+        sink.push(triple)?;
+    }
+    sink.finish()
+}
+
+/// Serializes the triples in N-Triples into a string
+pub fn ntriples_to_string<'a>(triples: impl IntoIterator<Item = &'a Triple>) -> String {
+    let mut buffer = Vec::default();
+    for triple in triples {
+        write_triple(&mut buffer, triple, EscapeStyle::Utf8)
+            .expect("Writing to a Vec should not fail");
+    }
+    String::from_utf8(buffer).expect("The N-Triples writer should output UTF-8")
+}
+
+/// Serializes the quads in [N-Quads](https://www.w3.org/TR/n-quads/) into a string
+pub fn nquads_to_string<'a>(quads: impl IntoIterator<Item = &'a Quad>) -> String {
+    let mut buffer = Vec::default();
+    for quad in quads {
+        write_statement(
+            &mut buffer,
+            quad,
+            quad.graph_name().as_ref(),
+            EscapeStyle::Utf8,
+        )
+        .expect("Writing to a Vec should not fail");
+    }
+    String::from_utf8(buffer).expect("The N-Quads writer should output UTF-8")
+}
+
+/// Serializes the quad as a [N-Quads](https://www.w3.org/TR/n-quads/) line, without the line break
+///
+/// Unlike `Display`, the IRIs and the literals are escaped following the N-Quads grammar.
+pub fn quad_to_nquads_line(quad: &Quad) -> String {
+    let mut line = nquads_to_string(Some(quad));
+    line.pop();
+    line
+}
+
+/// How the N-Triples writer escapes non-ASCII characters
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Hash, Default)]
+pub enum EscapeStyle {
+    /// Non-ASCII characters are written as is in UTF-8, as recommended by the N-Triples specification
+    #[default]
+    Utf8,
+    /// Non-ASCII characters of IRIs and literals are written using `\uXXXX` or `\UXXXXXXXX` escapes.
+    /// Blank node labels are written as is because N-Triples does not allow escapes in them.
+    AsciiOnly,
+}
+
+/// A `TripleSink` writing the triples in N-Triples
+pub struct NTriplesSink<W: Write> {
+    writer: W,
+    escape_style: EscapeStyle,
+}
+
+impl<W: Write> NTriplesSink<W> {
+    pub fn new(writer: W) -> Self {
+        NTriplesSink::with_escape_style(writer, EscapeStyle::default())
+    }
+
+    pub fn with_escape_style(writer: W, escape_style: EscapeStyle) -> Self {
+        NTriplesSink {
+            writer,
+            escape_style,
+        }
+    }
+
+    /// Returns the underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> TripleSink for NTriplesSink<W> {
+    fn push(&mut self, triple: &Triple) -> RioResult<()> {
+        write_triple(&mut self.writer, triple, self.escape_style)?;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> RioResult<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+fn write_triple(
+    writer: &mut impl Write,
+    triple: &Triple,
+    escape_style: EscapeStyle,
+) -> io::Result<()> {
+    write_statement(writer, triple, None, escape_style)
+}
+
+fn write_statement(
+    writer: &mut impl Write,
+    triple: &impl TripleLike,
+    graph_name: Option<&NamedOrBlankNode>,
+    escape_style: EscapeStyle,
+) -> io::Result<()> {
+    write_subject(writer, triple.subject(), escape_style)?;
+    write!(writer, " ")?;
+    write_iri(writer, triple.predicate(), escape_style)?;
+    write!(writer, " ")?;
+    write_object(writer, triple.object(), escape_style)?;
+    if let Some(graph_name) = graph_name {
+        write!(writer, " ")?;
+        write_subject(writer, graph_name, escape_style)?;
+    }
+    writeln!(writer, " .")
+}
+
+fn write_object(writer: &mut impl Write, term: &Term, escape_style: EscapeStyle) -> io::Result<()> {
+    match term {
+        Term::NamedNode(node) => write_iri(writer, node, escape_style),
+        Term::BlankNode(node) => write!(writer, "{}", node),
+        Term::Literal(literal) => write_literal(writer, literal, escape_style),
+    }
+}
+
+fn write_subject(
+    writer: &mut impl Write,
+    node: &NamedOrBlankNode,
+    escape_style: EscapeStyle,
+) -> io::Result<()> {
+    match node {
+        NamedOrBlankNode::NamedNode(node) => write_iri(writer, node, escape_style),
+        NamedOrBlankNode::BlankNode(node) => write!(writer, "{}", node),
+    }
+}
+
+fn write_iri(
+    writer: &mut impl Write,
+    node: &NamedNode,
+    escape_style: EscapeStyle,
+) -> io::Result<()> {
+    write!(writer, "<")?;
+    write_iri_content(writer, node.value(), escape_style)?;
+    write!(writer, ">")
+}
+
+/// Writes the IRI with the characters that are not allowed between `<` and `>` in N-Triples and Turtle written as [UCHAR](https://www.w3.org/TR/n-triples/#grammar-production-UCHAR) escapes
+pub(crate) fn write_iri_content(
+    writer: &mut impl Write,
+    iri: &str,
+    escape_style: EscapeStyle,
+) -> io::Result<()> {
+    for c in iri.chars() {
+        match c {
+            '\u{00}'..='\u{20}' | '<' | '>' | '"' | '{' | '}' | '|' | '^' | '`' | '\\' => {
+                write_uchar(writer, c)?
+            }
+            c => write_char(writer, c, escape_style)?,
+        }
+    }
+    Ok(())
+}
+
+fn write_literal(
+    writer: &mut impl Write,
+    literal: &Literal,
+    escape_style: EscapeStyle,
+) -> io::Result<()> {
+    write!(writer, "\"")?;
+    write_string_content(writer, literal.value(), escape_style)?;
+    write!(writer, "\"")?;
+    match literal {
+        Literal::SimpleLiteral(_) => Ok(()),
+        Literal::LanguageTaggedString { language, .. } => write!(writer, "@{}", language),
+        Literal::TypedLiteral { datatype, .. } => {
+            write!(writer, "^^")?;
+            write_iri(writer, datatype, escape_style)
+        }
+    }
+}
+
+/// Writes the content of a `"` quoted string literal of N-Triples and Turtle
+///
+/// The characters that have an [ECHAR](https://www.w3.org/TR/n-triples/#grammar-production-ECHAR) escape use it, the other control characters use an [UCHAR](https://www.w3.org/TR/n-triples/#grammar-production-UCHAR) escape.
+pub(crate) fn write_string_content(
+    writer: &mut impl Write,
+    value: &str,
+    escape_style: EscapeStyle,
+) -> io::Result<()> {
+    for c in value.chars() {
+        match c {
+            '"' => write!(writer, "\\\"")?,
+            '\\' => write!(writer, "\\\\")?,
+            '\n' => write!(writer, "\\n")?,
+            '\r' => write!(writer, "\\r")?,
+            '\t' => write!(writer, "\\t")?,
+            '\u{08}' => write!(writer, "\\b")?,
+            '\u{0C}' => write!(writer, "\\f")?,
+            '\u{00}'..='\u{1F}' | '\u{7F}' => write_uchar(writer, c)?,
+            c => write_char(writer, c, escape_style)?,
+        }
+    }
+    Ok(())
+}
+
+fn write_char(writer: &mut impl Write, c: char, escape_style: EscapeStyle) -> io::Result<()> {
+    if escape_style == EscapeStyle::AsciiOnly && !c.is_ascii() {
+        write_uchar(writer, c)
+    } else {
+        write!(writer, "{}", c)
+    }
+}
+
+fn write_uchar(writer: &mut impl Write, c: char) -> io::Result<()> {
+    if u32::from(c) <= 0xFFFF {
+        write!(writer, "\\u{:04X}", u32::from(c))
+    } else {
+        write!(writer, "\\U{:08X}", u32::from(c))
+    }
+}
+
+// The `Debug` implementations of the terms write them in N-Triples, with the N-Triples escaping.
+// With `{:#?}`, the terms, triples and quads are written as structures of N-Triples terms.
+
+/// Writes the N-Triples serialization produced by `write` in the formatter
+fn debug_ntriples(
+    f: &mut fmt::Formatter,
+    write: impl FnOnce(&mut Vec<u8>) -> io::Result<()>,
+) -> fmt::Result {
+    let mut buffer = Vec::default();
+    write(&mut buffer).map_err(|_| fmt::Error)?;
+    f.write_str(String::from_utf8_lossy(&buffer).trim_end())
+}
+
+impl fmt::Debug for NamedNode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        debug_ntriples(f, |buffer| write_iri(buffer, self, EscapeStyle::Utf8))
+    }
+}
+
+impl fmt::Debug for BlankNode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self)
+    }
+}
+
+impl fmt::Debug for Literal {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        debug_ntriples(f, |buffer| write_literal(buffer, self, EscapeStyle::Utf8))
+    }
+}
+
+impl fmt::Debug for NamedOrBlankNode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        debug_ntriples(f, |buffer| write_subject(buffer, self, EscapeStyle::Utf8))
+    }
+}
+
+impl fmt::Debug for Term {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !f.alternate() {
+            return debug_ntriples(f, |buffer| write_object(buffer, self, EscapeStyle::Utf8));
+        }
+        match self {
+            Term::NamedNode(node) => f.debug_tuple("NamedNode").field(node).finish(),
+            Term::BlankNode(node) => f.debug_tuple("BlankNode").field(node).finish(),
+            Term::Literal(literal) => f.debug_tuple("Literal").field(literal).finish(),
+        }
+    }
+}
+
+impl fmt::Debug for Triple {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !f.alternate() {
+            return debug_ntriples(f, |buffer| write_triple(buffer, self, EscapeStyle::Utf8));
+        }
+        f.debug_struct("Triple")
+            .field("subject", self.subject())
+            .field("predicate", self.predicate())
+            .field("object", self.object())
+            .finish()
+    }
+}
+
+impl fmt::Debug for Quad {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if !f.alternate() {
+            return debug_ntriples(f, |buffer| {
+                write_statement(buffer, self, self.graph_name().as_ref(), EscapeStyle::Utf8)
+            });
+        }
+        f.debug_struct("Quad")
+            .field("subject", self.subject())
+            .field("predicate", self.predicate())
+            .field("object", self.object())
+            .field("graph_name", self.graph_name())
+            .finish()
+    }
+}
@@ -0,0 +1,293 @@
+/// Implements a writer for https://www.w3.org/TR/rdf-syntax-grammar/
+use model::data::*;
+use rio::*;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::io::Write;
+
+const RDF_NAMESPACE: &str = "http://www.w3.org/1999/02/22-rdf-syntax-ns#";
+
+/// Writes the triples in RDF/XML with one `rdf:Description` element per subject
+///
+/// The `prefixes` map associates prefix names (without the trailing `:`) to namespace IRIs.
+/// They are declared on the root `rdf:RDF` element and used to write the predicates as XML qualified names.
+/// Namespaces of predicates that are not in the map are declared with generated `ns1`, `ns2`... prefixes not used by the map.
+/// The prefixes that are not valid XML prefixes, like the empty prefix, are ignored.
+/// The blank node ids that are not XML names, like `1`, are replaced by valid `rdf:nodeID` values.
+/// Fails if a value contains a control character that XML 1.0 does not allow.
+pub fn write_rdfxml<'a, W: Write>(
+    triples: impl IntoIterator<Item = &'a Triple>,
+    prefixes: &HashMap<String, String>,
+    mut sink: W,
+) -> RioResult<()> {
+    // Group triples by subject, keeping the order in which the subjects first appear
+    let mut descriptions: Vec<(&NamedOrBlankNode, Vec<&Triple>)> = Vec::default();
+    let mut description_by_subject: HashMap<&NamedOrBlankNode, usize> = HashMap::default();
+    for triple in triples {
+        let position = *description_by_subject
+            .entry(triple.subject())
+            .or_insert_with(|| {
+                descriptions.push((triple.subject(), Vec::default()));
+                descriptions.len() - 1
+            });
+        descriptions[position].1.push(triple);
+    }
+
+    let mut prefix_by_namespace: HashMap<&str, String> = prefixes
+        .iter()
+        .filter(|(prefix, _)| prefix.as_str() != "rdf" && is_valid_prefix(prefix))
+        .map(|(prefix, namespace)| (namespace.as_str(), prefix.clone()))
+        .collect();
+    prefix_by_namespace.insert(RDF_NAMESPACE, "rdf".to_owned());
+    let mut generated_prefixes_count = 0;
+    for (_, triples) in &descriptions {
+        for triple in triples {
+            let (namespace, _) = split_qualified_name(triple.predicate())?;
+            if !prefix_by_namespace.contains_key(namespace) {
+                let prefix = loop {
+                    generated_prefixes_count += 1;
+                    let prefix = format!("ns{}", generated_prefixes_count);
+                    if !prefixes.contains_key(&prefix) {
+                        break prefix;
+                    }
+                };
+                prefix_by_namespace.insert(namespace, prefix);
+            }
+        }
+    }
+
+    let mut declarations: Vec<(&String, &str)> = prefix_by_namespace
+        .iter()
+        .map(|(namespace, prefix)| (prefix, *namespace))
+        .collect();
+    declarations.sort();
+    write!(sink, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n<rdf:RDF")?;
+    for (prefix, namespace) in declarations {
+        write!(sink, " xmlns:{}=\"{}\"", prefix, escape_xml(namespace)?)?;
+    }
+    writeln!(sink, ">")?;
+
+    let node_ids = node_ids(&descriptions);
+    for (subject, triples) in &descriptions {
+        match subject {
+            NamedOrBlankNode::NamedNode(node) => writeln!(
+                sink,
+                "  <rdf:Description rdf:about=\"{}\">",
+                escape_xml(node.value())?
+            )?,
+            NamedOrBlankNode::BlankNode(node) => writeln!(
+                sink,
+                "  <rdf:Description rdf:nodeID=\"{}\">",
+                node_ids[node]
+            )?,
+        }
+        for triple in triples {
+            let (namespace, local_name) = split_qualified_name(triple.predicate())?;
+            let name = format!("{}:{}", prefix_by_namespace[namespace], local_name);
+            match triple.object() {
+                Term::NamedNode(node) => writeln!(
+                    sink,
+                    "    <{} rdf:resource=\"{}\"/>",
+                    name,
+                    escape_xml(node.value())?
+                )?,
+                Term::BlankNode(node) => {
+                    writeln!(sink, "    <{} rdf:nodeID=\"{}\"/>", name, node_ids[node])?
+                }
+                Term::Literal(Literal::SimpleLiteral(value)) => {
+                    writeln!(sink, "    <{}>{}</{}>", name, escape_xml(value)?, name)?
+                }
+                Term::Literal(Literal::LanguageTaggedString { value, language }) => writeln!(
+                    sink,
+                    "    <{} xml:lang=\"{}\">{}</{}>",
+                    name,
+                    escape_xml(language)?,
+                    escape_xml(value)?,
+                    name
+                )?,
+                Term::Literal(Literal::TypedLiteral { value, datatype }) => writeln!(
+                    sink,
+                    "    <{} rdf:datatype=\"{}\">{}</{}>",
+                    name,
+                    escape_xml(datatype.value())?,
+                    escape_xml(value)?,
+                    name
+                )?,
+            }
+        }
+        writeln!(sink, "  </rdf:Description>")?;
+    }
+    writeln!(sink, "</rdf:RDF>")?;
+    Ok(())
+}
+
+/// A `TripleSink` writing the triples in RDF/XML
+///
+/// The triples are buffered until `finish` is called in order to group them by subject.
+pub struct RdfXmlSink<W: Write> {
+    writer: W,
+    prefixes: HashMap<String, String>,
+    triples: Vec<Triple>,
+}
+
+impl<W: Write> RdfXmlSink<W> {
+    /// Builds a sink using the prefixes like `write_rdfxml`
+    pub fn new(writer: W, prefixes: HashMap<String, String>) -> Self {
+        RdfXmlSink {
+            writer,
+            prefixes,
+            triples: Vec::default(),
+        }
+    }
+}
+
+impl<W: Write> TripleSink for RdfXmlSink<W> {
+    fn push(&mut self, triple: &Triple) -> RioResult<()> {
+        self.triples.push(triple.clone());
+        Ok(())
+    }
+
+    fn finish(&mut self) -> RioResult<()> {
+        write_rdfxml(&self.triples, &self.prefixes, &mut self.writer)?;
+        self.triples.clear();
+        Ok(())
+    }
+}
+
+/// Chooses the `rdf:nodeID` of each blank node, which should be a XML [NCName](https://www.w3.org/TR/xml-names/#NT-NCName)
+///
+/// The ids that are NCNames are kept. The other ones, like the `1`, `2`... generated by `DataFactory::default()`, are prefixed by `b`,
+/// or replaced by `b1`, `b2`... if the prefixed id is still not a NCName or is already used.
+fn node_ids<'a>(
+    descriptions: &[(&'a NamedOrBlankNode, Vec<&'a Triple>)],
+) -> HashMap<&'a BlankNode, String> {
+    let mut blank_nodes: Vec<&BlankNode> = Vec::default();
+    for (subject, triples) in descriptions {
+        if let NamedOrBlankNode::BlankNode(node) = subject {
+            blank_nodes.push(node);
+        }
+        for triple in triples {
+            if let Term::BlankNode(node) = triple.object() {
+                blank_nodes.push(node);
+            }
+        }
+    }
+    let mut used_ids: HashSet<String> = blank_nodes
+        .iter()
+        .map(|node| node.value())
+        .filter(|id| is_valid_prefix(id))
+        .map(str::to_owned)
+        .collect();
+    let mut node_ids = HashMap::default();
+    let mut generated_ids_count = 0;
+    for node in blank_nodes {
+        if node_ids.contains_key(node) {
+            continue;
+        }
+        let id = if is_valid_prefix(node.value()) {
+            node.value().to_owned()
+        } else {
+            let prefixed = format!("b{}", node.value());
+            let id = if is_valid_prefix(&prefixed) && !used_ids.contains(&prefixed) {
+                prefixed
+            } else {
+                loop {
+                    generated_ids_count += 1;
+                    let id = format!("b{}", generated_ids_count);
+                    if !used_ids.contains(&id) {
+                        break id;
+                    }
+                }
+            };
+            used_ids.insert(id.clone());
+            id
+        };
+        node_ids.insert(node, id);
+    }
+    node_ids
+}
+
+/// Splits a predicate IRI into a namespace and the longest possible XML local name
+fn split_qualified_name(predicate: &NamedNode) -> RioResult<(&str, &str)> {
+    let iri = predicate.value();
+    let local_name_start = iri
+        .char_indices()
+        .rev()
+        .take_while(|(_, c)| is_name_char(*c))
+        .last()
+        .map(|(i, _)| i)
+        .unwrap_or_else(|| iri.len());
+    // The local name should not start with a digit or a punctuation character
+    match iri[local_name_start..]
+        .char_indices()
+        .find(|(_, c)| is_name_start_char(*c))
+    {
+        Some((offset, _)) => Ok(iri.split_at(local_name_start + offset)),
+        None => Err(RioError::new(format!(
+            "The predicate {} could not be written as a XML qualified name",
+            predicate
+        ))),
+    }
+}
+
+/// Checks if the character is allowed at the beginning of a XML [NCName](https://www.w3.org/TR/xml-names/#NT-NCName)
+fn is_name_start_char(c: char) -> bool {
+    matches!(c,
+        'A'..='Z'
+        | '_'
+        | 'a'..='z'
+        | '\u{C0}'..='\u{D6}'
+        | '\u{D8}'..='\u{F6}'
+        | '\u{F8}'..='\u{2FF}'
+        | '\u{370}'..='\u{37D}'
+        | '\u{37F}'..='\u{1FFF}'
+        | '\u{200C}'..='\u{200D}'
+        | '\u{2070}'..='\u{218F}'
+        | '\u{2C00}'..='\u{2FEF}'
+        | '\u{3001}'..='\u{D7FF}'
+        | '\u{F900}'..='\u{FDCF}'
+        | '\u{FDF0}'..='\u{FFFD}'
+        | '\u{10000}'..='\u{EFFFF}')
+}
+
+/// Checks if the character is allowed in a XML [NCName](https://www.w3.org/TR/xml-names/#NT-NCName)
+fn is_name_char(c: char) -> bool {
+    match c {
+        '-' | '.' | '0'..='9' | '\u{B7}' | '\u{0300}'..='\u{036F}' | '\u{203F}'..='\u{2040}' => {
+            true
+        }
+        c => is_name_start_char(c),
+    }
+}
+
+/// Checks if the prefix is a XML [NCName](https://www.w3.org/TR/xml-names/#NT-NCName) that can be declared with `xmlns:`
+fn is_valid_prefix(prefix: &str) -> bool {
+    let mut chars = prefix.chars();
+    chars.next().is_some_and(is_name_start_char) && chars.all(is_name_char)
+}
+
+/// Escapes the value for XML attributes and text
+///
+/// The whitespaces other than the space are written as character references so that XML parsers do not normalize them.
+fn escape_xml(value: &str) -> RioResult<String> {
+    let mut escaped = String::with_capacity(value.len());
+    for c in value.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\t' => escaped.push_str("&#9;"),
+            '\n' => escaped.push_str("&#10;"),
+            '\r' => escaped.push_str("&#13;"),
+            '\u{0}'..='\u{1F}' | '\u{FFFE}' | '\u{FFFF}' => {
+                return Err(RioError::new(format!(
+                    "The character U+{:04X} is not allowed in XML 1.0",
+                    u32::from(c)
+                )))
+            }
+            c => escaped.push(c),
+        }
+    }
+    Ok(escaped)
+}
@@ -5,38 +5,1135 @@ mod grammar {
 }
 
 use model::data::*;
+use model::graph::Graph;
+use model::iri::PrefixedIriResolver;
+use rio::ntriples::EscapeStyle;
 use rio::*;
+use std::collections::BTreeMap;
 use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+use std::io;
 use std::io::BufReader;
 use std::io::Read;
+use std::io::Write;
+use std::ops::Range;
+use std::path::Path;
+use std::path::PathBuf;
+use std::str;
 
 //TODO: make private
+#[derive(Clone, Default)]
 pub struct ParserState {
-    pub base_uri: String,
-    pub namespaces: HashMap<String, String>,
+    /// The base IRI and the prefixes declared so far
+    pub iri_resolver: PrefixedIriResolver,
     pub cur_subject: Vec<NamedOrBlankNode>,
     pub cur_predicate: Vec<NamedNode>,
+    pub located_error: Option<TurtleSyntaxError>,
+    /// The ranges of the triples of the graph blocks, if the graph blocks are enabled
+    pub graphs: Option<Vec<GraphRange>>,
+    /// The name of the graph block being parsed and the position of its first triple
+    pub cur_graph: Option<(NamedOrBlankNode, usize)>,
+}
+
+/// The triples of a graph block in the triple buffer of the parser, with the name of the graph
+pub type GraphRange = (Range<usize>, NamedOrBlankNode);
+
+/// Options of the Turtle parser
+///
+/// They are built from the default options with the `with_` methods, like `TurtleOptions::default().with_limit(10)`.
+#[derive(Debug, Clone)]
+#[non_exhaustive]
+pub struct TurtleOptions {
+    /// Accepts some invalid inputs emitted by common generators.
+    /// The affected tokens are the `a` keyword for `rdf:type`, that could be written `A`,
+    /// and the `true` and `false` boolean keywords, that could be written in any case like `TRUE` or `False`.
+    pub lenient: bool,
+    /// Stops the parsing once this number of triples is read and returns them.
+    /// The rest of the document is not read, so its syntax errors are not reported.
+    pub limit: Option<usize>,
+    /// The size of the buffer used to read the document, `DEFAULT_BUFFER_SIZE` by default.
+    /// A size of 0 is read as 1.
+    pub buffer_size: usize,
+    /// Replaces the lexical forms of the literals by their canonical forms, like `"01"^^xsd:integer` by `"1"^^xsd:integer`.
+    /// See `Literal::canonical` for the normalized datatypes.
+    pub normalize_literals: bool,
+    /// Allows `read_turtle_quads` to read the `GRAPH <g> { ... }` and `<g> { ... }` blocks of [TriG](https://www.w3.org/TR/trig/).
+    /// The triples of a block are returned in the graph named by the block.
+    /// The readers of triples always reject the graph blocks.
+    pub graph_blocks: bool,
+    /// What `read_turtle_quads` does with the graph blocks named by a blank node, like `_:g { ... }`
+    pub blank_node_graph_names: BlankNodeGraphNames,
+}
+
+/// The handling of the blank node graph names by `read_turtle_quads`
+///
+/// Some stores only support IRI graph names.
+#[derive(Eq, PartialEq, Debug, Clone, Default)]
+pub enum BlankNodeGraphNames {
+    /// Keeps the blank node graph names
+    #[default]
+    Keep,
+    /// Fails with a syntax error on the first blank node graph name
+    Reject,
+    /// Replaces the blank node graph names by skolem IRIs built from the given base, like `Dataset::skolemize_graph_names`
+    Skolemize(String),
+}
+
+impl Default for TurtleOptions {
+    fn default() -> Self {
+        TurtleOptions {
+            lenient: false,
+            limit: None,
+            buffer_size: DEFAULT_BUFFER_SIZE,
+            normalize_literals: false,
+            graph_blocks: false,
+            blank_node_graph_names: BlankNodeGraphNames::default(),
+        }
+    }
+}
+
+impl TurtleOptions {
+    /// Sets if some common invalid inputs are accepted (see `lenient`)
+    pub fn with_lenient(mut self, lenient: bool) -> Self {
+        self.lenient = lenient;
+        self
+    }
+
+    /// Sets the maximal number of triples to read
+    pub fn with_limit(mut self, limit: usize) -> Self {
+        self.limit = Some(limit);
+        self
+    }
+
+    /// Sets the size of the buffer used to read the document
+    pub fn with_buffer_size(mut self, buffer_size: usize) -> Self {
+        self.buffer_size = buffer_size;
+        self
+    }
+
+    /// Sets if the literals are replaced by their canonical forms
+    pub fn with_normalize_literals(mut self, normalize_literals: bool) -> Self {
+        self.normalize_literals = normalize_literals;
+        self
+    }
+
+    /// Sets if the graph blocks are allowed by `read_turtle_quads`
+    pub fn with_graph_blocks(mut self, graph_blocks: bool) -> Self {
+        self.graph_blocks = graph_blocks;
+        self
+    }
+
+    /// Sets what `read_turtle_quads` does with the blank node graph names
+    pub fn with_blank_node_graph_names(
+        mut self,
+        blank_node_graph_names: BlankNodeGraphNames,
+    ) -> Self {
+        self.blank_node_graph_names = blank_node_graph_names;
+        self
+    }
 }
 
 pub fn read_turtle<'a, R: Read + 'a>(
     source: R,
     data_factory: &'a DataFactory,
 ) -> RioResult<impl Iterator<Item = Triple>> {
-    let factory = data_factory.clone(); //TODO: try to avoid clone here
-    let mut state = ParserState {
-        base_uri: String::default(),
-        namespaces: HashMap::default(),
-        cur_subject: Vec::default(),
-        cur_predicate: Vec::default(),
-    };
+    read_turtle_with_options(source, data_factory, &TurtleOptions::default())
+}
+
+/// Reads a Turtle document like `read_turtle` using the given parser options
+pub fn read_turtle_with_options<'a, R: Read + 'a>(
+    source: R,
+    data_factory: &'a DataFactory,
+    options: &TurtleOptions,
+) -> RioResult<impl Iterator<Item = Triple>> {
+    if let Some(limit) = options.limit {
+        return read_turtle_prefix(source, data_factory, options, limit).map(Vec::into_iter);
+    }
+    let (triples, _) = read_document(source, data_factory, options, ParserState::default())?;
+    Ok(triples.into_iter())
+}
+
+/// Reads a Turtle document as quads
+///
+/// The triples outside of the graph blocks are in the default graph.
+/// The graph blocks are only allowed if `options.graph_blocks` is set, like exported by some SPARQL Update tools.
+/// The blank node graph names are handled following `options.blank_node_graph_names`.
+/// The whole document is read before returning the quads, even if `options.limit` is set.
+pub fn read_turtle_quads<'a, R: Read + 'a>(
+    source: R,
+    data_factory: &'a DataFactory,
+    options: &TurtleOptions,
+) -> RioResult<impl Iterator<Item = Quad>> {
+    let mut state = ParserState::default();
+    if options.graph_blocks {
+        state.graphs = Some(Vec::default());
+    }
+    let (triples, state) = read_document(source, data_factory, options, state)?;
+    // The graph ranges are sorted and disjoint
+    let mut graphs = state.graphs.unwrap_or_default().into_iter().peekable();
+    let default_graph = None;
+    Ok(triples
+        .into_iter()
+        .enumerate()
+        .map(move |(i, triple)| {
+            while graphs.peek().is_some_and(|(range, _)| range.end <= i) {
+                graphs.next();
+            }
+            match graphs.peek() {
+                Some((range, graph_name)) if range.contains(&i) => {
+                    triple.in_graph(&Some(graph_name.clone()))
+                }
+                _ => triple.in_graph(&default_graph),
+            }
+        })
+        .take(options.limit.unwrap_or(usize::MAX)))
+}
+
+/// Reads and parses a whole Turtle document starting with the given parser state
+///
+/// Returns the triples and the parser state at the end of the document.
+fn read_document(
+    source: impl Read,
+    data_factory: &DataFactory,
+    options: &TurtleOptions,
+    mut state: ParserState,
+) -> RioResult<(Vec<Triple>, ParserState)> {
     let mut string_buffer = String::default();
     let mut triple_buffer = Vec::default();
-    match BufReader::new(source).read_to_string(&mut string_buffer) {
-        Ok(_) => match grammar::turtleDoc(&string_buffer, &mut state, &mut triple_buffer, &factory)
+    BufReader::with_capacity(options.buffer_size.max(1), source)
+        .read_to_string(&mut string_buffer)?;
+    match grammar::turtleDoc(
+        &string_buffer,
+        &mut state,
+        &mut triple_buffer,
+        data_factory,
+        options,
+    ) {
+        Ok(_) => Ok((triple_buffer, state)),
+        Err(error) => Err(syntax_error(error, state, 0)),
+    }
+}
+
+/// Reads the `limit` first triples of a Turtle document, stopping the reading once they are parsed
+fn read_turtle_prefix(
+    mut source: impl Read,
+    data_factory: &DataFactory,
+    options: &TurtleOptions,
+    limit: usize,
+) -> RioResult<Vec<Triple>> {
+    let mut parser = TurtleParser::with_options(data_factory, options.clone());
+    let mut triples = Vec::default();
+    let mut chunk = vec![0; options.buffer_size.max(1)];
+    while triples.len() < limit {
+        let length = match source.read(&mut chunk) {
+            Ok(0) => {
+                triples.extend(parser.finish()?);
+                break;
+            }
+            Ok(length) => length,
+            Err(ref error) if error.kind() == io::ErrorKind::Interrupted => continue,
+            Err(error) => return Err(error.into()),
+        };
+        triples.extend(parser.push_chunk(&chunk[..length]));
+    }
+    triples.truncate(limit);
+    Ok(triples)
+}
+
+/// Parses a Turtle document and calls `on_triple` on each triple as soon as its statement is parsed
+///
+/// The parsing stops at the first error returned by `on_triple` and this error is returned.
+/// Only the incomplete statement at the end of the bytes read so far is kept in memory.
+pub fn parse_turtle_with<R: Read, E: From<RioError>>(
+    mut source: R,
+    data_factory: &DataFactory,
+    mut on_triple: impl FnMut(Triple) -> Result<(), E>,
+) -> Result<(), E> {
+    let mut parser = TurtleParser::new(data_factory);
+    let mut chunk = [0; 8192];
+    loop {
+        let length = match source.read(&mut chunk) {
+            Ok(0) => break,
+            Ok(length) => length,
+            Err(ref error) if error.kind() == io::ErrorKind::Interrupted => continue,
+            Err(error) => return Err(RioError::from(error).into()),
+        };
+        for triple in parser.push_chunk(&chunk[..length]) {
+            on_triple(triple)?;
+        }
+    }
+    for triple in parser.finish()? {
+        on_triple(triple)?;
+    }
+    Ok(())
+}
+
+/// Loads the triples of a Turtle document into the graph
+///
+/// `progress` is called with the number of triples parsed so far every `interval` triples and at the end of the parsing,
+/// unless the last call already gave the final count.
+pub fn load_turtle_with_progress(
+    graph: &mut Graph,
+    source: impl Read,
+    data_factory: &DataFactory,
+    interval: usize,
+    mut progress: impl FnMut(usize),
+) -> RioResult<()> {
+    let interval = interval.max(1);
+    let mut count = 0;
+    parse_turtle_with(source, data_factory, |triple| {
+        graph.insert(triple);
+        count += 1;
+        if count % interval == 0 {
+            progress(count);
+        }
+        Ok::<(), RioError>(())
+    })?;
+    // The last periodic report already gave the final count
+    if count == 0 || count % interval != 0 {
+        progress(count);
+    }
+    Ok(())
+}
+
+/// Reads the base IRI and the prefixes declared by the directives at the beginning of a Turtle document
+///
+/// The reading stops at the first statement that is not a directive, so only the beginning of the document is read.
+/// The prefixes are returned without their trailing `:`.
+pub fn read_prefixes<R: Read>(
+    mut source: R,
+) -> RioResult<(Option<String>, HashMap<String, String>)> {
+    let data_factory = DataFactory::default();
+    let options = TurtleOptions::default();
+    let mut state = ParserState::default();
+    let mut buffer = Vec::default();
+    let mut chunk = [0; 8192];
+    let mut is_end = false;
+    // The position in the buffer of the end of the last directive read
+    let mut consumed = 0;
+    loop {
+        let rest = &buffer[consumed..];
+        let text = match str::from_utf8(rest) {
+            Ok(text) => text,
+            // An incomplete character at the end of the buffer is read with the next chunk
+            Err(error) => str::from_utf8(&rest[..error.valid_up_to()]).map_err(RioError::new)?,
+        };
+        let error = match grammar::leading_directive(
+            text,
+            &mut state,
+            &mut Vec::default(),
+            &data_factory,
+            &options,
+        ) {
+            Ok(end) => {
+                consumed += end;
+                continue;
+            }
+            Err(error) => error,
+        };
+        // The directive may end in the bytes not read yet.
+        // A keyword cut by the end of the text fails at its start, so the failures close to the end are also retried.
+        if text.len() - error.offset < "@prefix".len() && !is_end {
+            match source.read(&mut chunk) {
+                Ok(0) => is_end = true,
+                Ok(length) => buffer.extend_from_slice(&chunk[..length]),
+                Err(ref error) if error.kind() == io::ErrorKind::Interrupted => (),
+                Err(error) => return Err(error.into()),
+            }
+            continue;
+        }
+        if grammar::leading_directive_keyword(
+            text,
+            &mut state,
+            &mut Vec::default(),
+            &data_factory,
+            &options,
+        )
+        .is_ok()
         {
-            Ok(_) => Ok(triple_buffer.into_iter()),
-            Err(error) => Err(RioError::new(error)),
+            return Err(syntax_error_after(error, state, &buffer[..consumed]));
+        }
+        break;
+    }
+    let base = state.iri_resolver.base().map(str::to_owned);
+    Ok((base, state.iri_resolver.prefixes().clone()))
+}
+
+/// Builds the error of a grammar parsing starting after the `before` bytes of the document
+fn syntax_error_after(
+    mut error: grammar::ParseError,
+    state: ParserState,
+    before: &[u8],
+) -> RioError {
+    let before = String::from_utf8_lossy(before);
+    // The grammar positions are relative to the parsed text start
+    if error.line == 1 {
+        error.column += before
+            .rsplit('\n')
+            .next()
+            .map_or(0, |line| line.chars().count());
+    }
+    error.line += before.matches('\n').count();
+    syntax_error(error, state, before.len())
+}
+
+/// The kinds of `TurtleSyntaxError`
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Hash)]
+pub enum TurtleSyntaxErrorKind {
+    /// The document does not follow the Turtle grammar
+    InvalidSyntax,
+    /// An IRI or a literal is invalid
+    InvalidTerm,
+    /// The document uses a [Notation3](https://www.w3.org/TeamSubmission/n3/) construct that is not in Turtle, like `=>` or `{ }` formulae
+    UnsupportedN3Feature,
+    /// The document uses a [TriG](https://www.w3.org/TR/trig/) graph block like `GRAPH <g> { }` or `<g> { }` that could not be read as triples
+    UnsupportedTrigFeature,
+}
+
+/// A syntax error in a Turtle document, with the span of the invalid token
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub struct TurtleSyntaxError {
+    pub kind: TurtleSyntaxErrorKind,
+    pub message: String,
+    /// The byte offset of the beginning of the invalid token in the document
+    pub start: usize,
+    /// The byte offset of the end of the invalid token in the document
+    pub end: usize,
+}
+
+impl fmt::Display for TurtleSyntaxError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} at bytes {}..{}", self.message, self.start, self.end)
+    }
+}
+
+impl Error for TurtleSyntaxError {}
+
+/// Converts a grammar error into a `TurtleSyntaxError`, using the span of the invalid term that caused it if there is one
+fn syntax_error(error: grammar::ParseError, state: ParserState, offset: usize) -> RioError {
+    let error = match state.located_error {
+        Some(located_error) if located_error.end == error.offset => located_error,
+        _ => TurtleSyntaxError {
+            kind: TurtleSyntaxErrorKind::InvalidSyntax,
+            message: error.to_string(),
+            start: error.offset,
+            end: error.offset,
         },
-        Err(error) => Err(RioError::new(error)),
+    };
+    RioError::new(TurtleSyntaxError {
+        start: error.start + offset,
+        end: error.end + offset,
+        ..error
+    })
+}
+
+/// An error raised by `read_turtle_path`, with the path of the file that failed to be loaded
+#[derive(Debug)]
+pub enum TurtleError {
+    /// The file could not be read
+    Io { path: PathBuf, error: io::Error },
+    /// The file is not a valid Turtle document
+    Syntax { path: PathBuf, error: RioError },
+}
+
+impl TurtleError {
+    /// The path of the file that failed to be loaded
+    pub fn path(&self) -> &Path {
+        match self {
+            TurtleError::Io { path, .. } | TurtleError::Syntax { path, .. } => path,
+        }
+    }
+}
+
+impl fmt::Display for TurtleError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TurtleError::Io { path, error } => {
+                write!(f, "Failed to read {}: {}", path.display(), error)
+            }
+            TurtleError::Syntax { path, error } => {
+                write!(f, "Failed to parse {}: {}", path.display(), error)
+            }
+        }
+    }
+}
+
+impl Error for TurtleError {
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            TurtleError::Io { error, .. } => Some(error),
+            TurtleError::Syntax { error, .. } => Some(error),
+        }
+    }
+}
+
+/// Reads the Turtle file at the given path and returns all its triples
+pub fn read_turtle_path(
+    path: impl AsRef<Path>,
+    data_factory: &DataFactory,
+) -> Result<Vec<Triple>, TurtleError> {
+    let path = path.as_ref();
+    let bytes = fs::read(path).map_err(|error| TurtleError::Io {
+        path: path.to_owned(),
+        error,
+    })?;
+    let triples = match read_turtle(bytes.as_slice(), data_factory) {
+        Ok(triples) => triples.collect(),
+        Err(error) => {
+            return Err(TurtleError::Syntax {
+                path: path.to_owned(),
+                error,
+            })
+        }
+    };
+    Ok(triples)
+}
+
+/// An error raised by `unescape_literal` on invalid escape sequences
+#[derive(Eq, PartialEq, Debug, Clone)]
+pub enum EscapeError {
+    /// A `\` is followed by a character that does not start an escape sequence
+    InvalidEscape(char),
+    /// The string ends in the middle of an escape sequence
+    UnexpectedEnd,
+    /// The hexadecimal digits of a `\u` or `\U` escape sequence are not a valid Unicode code point
+    InvalidCodePoint(String),
+}
+
+impl fmt::Display for EscapeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            EscapeError::InvalidEscape(c) => write!(f, "Invalid escape sequence \\{}", c),
+            EscapeError::UnexpectedEnd => write!(f, "Unfinished escape sequence"),
+            EscapeError::InvalidCodePoint(digits) => {
+                write!(f, "{} is not a valid Unicode code point", digits)
+            }
+        }
+    }
+}
+
+impl Error for EscapeError {}
+
+/// Escapes a string to be used as the content of a `"` quoted Turtle string literal
+///
+/// The characters that have an [ECHAR](https://www.w3.org/TR/turtle/#grammar-production-ECHAR) escape use it, the other control characters use an [UCHAR](https://www.w3.org/TR/turtle/#grammar-production-UCHAR) escape.
+pub fn escape_literal(value: &str) -> String {
+    escape_with(|buffer| ntriples::write_string_content(buffer, value, EscapeStyle::Utf8))
+}
+
+/// Decodes the [ECHAR](https://www.w3.org/TR/turtle/#grammar-production-ECHAR) and [UCHAR](https://www.w3.org/TR/turtle/#grammar-production-UCHAR) escape sequences of a Turtle string literal content
+pub fn unescape_literal(value: &str) -> Result<String, EscapeError> {
+    let mut unescaped = String::with_capacity(value.len());
+    let mut chars = value.chars();
+    while let Some(c) = chars.next() {
+        if c != '\\' {
+            unescaped.push(c);
+            continue;
+        }
+        match chars.next().ok_or(EscapeError::UnexpectedEnd)? {
+            't' => unescaped.push('\t'),
+            'b' => unescaped.push('\u{08}'),
+            'n' => unescaped.push('\n'),
+            'r' => unescaped.push('\r'),
+            'f' => unescaped.push('\u{0C}'),
+            '"' => unescaped.push('"'),
+            '\'' => unescaped.push('\''),
+            '\\' => unescaped.push('\\'),
+            'u' => unescaped.push(unescape_uchar(&mut chars, 4)?),
+            'U' => unescaped.push(unescape_uchar(&mut chars, 8)?),
+            c => return Err(EscapeError::InvalidEscape(c)),
+        }
+    }
+    Ok(unescaped)
+}
+
+fn unescape_uchar(chars: &mut str::Chars, length: usize) -> Result<char, EscapeError> {
+    let digits: String = chars.take(length).collect();
+    if digits.chars().count() < length {
+        return Err(EscapeError::UnexpectedEnd);
+    }
+    u32::from_str_radix(&digits, 16)
+        .ok()
+        .filter(|_| digits.chars().all(|c| c.is_ascii_hexdigit()))
+        .and_then(char::from_u32)
+        .ok_or(EscapeError::InvalidCodePoint(digits))
+}
+
+/// Serializes the triples in Turtle into a string, one triple per line
+///
+/// Only the `prefixes` used by the triples are declared.
+pub fn turtle_to_string<'a>(
+    triples: impl IntoIterator<Item = &'a Triple>,
+    prefixes: &HashMap<String, String>,
+) -> String {
+    let mut formatter = TermFormatter::new(prefixes);
+    let mut body = String::default();
+    for triple in triples {
+        let subject = formatter.subject(triple.subject());
+        let predicate = formatter.named_node(triple.predicate());
+        let object = formatter.term(triple.object());
+        body.push_str(&format!("{} {} {} .\n", subject, predicate, object));
+    }
+    let directives = formatter.prefix_directives();
+    if directives.is_empty() {
+        body
+    } else {
+        format!("{}\n{}", directives, body)
+    }
+}
+
+/// A `TripleSink` writing the triples in Turtle as soon as they are pushed, one triple per line
+///
+/// All the prefixes are declared at the beginning of the document, when the writer is built,
+/// so only the IRIs matching one of them are abbreviated and the other ones are written in full.
+/// Use `suggest_prefixes` on a sample of the IRIs to choose the prefixes.
+pub struct StreamingTurtleWriter<W: Write> {
+    writer: W,
+    prefixes: HashMap<String, String>,
+}
+
+impl<W: Write> StreamingTurtleWriter<W> {
+    /// Builds the writer and writes the `@prefix` directives of the valid `prefixes`, ordered by prefix
+    pub fn new(mut writer: W, prefixes: HashMap<String, String>) -> RioResult<Self> {
+        let mut declared: Vec<(&String, &String)> = prefixes
+            .iter()
+            .filter(|(prefix, _)| is_valid_prefix(prefix))
+            .collect();
+        declared.sort();
+        for (prefix, iri) in &declared {
+            writer.write_all(prefix_directive(prefix, iri).as_bytes())?;
+        }
+        if !declared.is_empty() {
+            writer.write_all(b"\n")?;
+        }
+        Ok(StreamingTurtleWriter { writer, prefixes })
+    }
+
+    /// Returns the underlying writer
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+impl<W: Write> TripleSink for StreamingTurtleWriter<W> {
+    fn push(&mut self, triple: &Triple) -> RioResult<()> {
+        let mut formatter = TermFormatter::new(&self.prefixes);
+        let subject = formatter.subject(triple.subject());
+        let predicate = formatter.named_node(triple.predicate());
+        let object = formatter.term(triple.object());
+        writeln!(self.writer, "{} {} {} .", subject, predicate, object)?;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> RioResult<()> {
+        self.writer.flush()?;
+        Ok(())
+    }
+}
+
+/// The indentation of the predicate lines written by `write_turtle_pretty`
+#[derive(Eq, PartialEq, Debug, Clone, Copy, Hash)]
+pub enum Indent {
+    /// Indents with the given number of spaces
+    Spaces(usize),
+    /// Indents with the given number of tabulations
+    Tabs(usize),
+}
+
+impl Default for Indent {
+    fn default() -> Self {
+        Indent::Spaces(4)
+    }
+}
+
+impl fmt::Display for Indent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let (c, width) = match self {
+            Indent::Spaces(width) => (' ', width),
+            Indent::Tabs(width) => ('\t', width),
+        };
+        for _ in 0..*width {
+            write!(f, "{}", c)?;
+        }
+        Ok(())
+    }
+}
+
+/// The configuration of `write_turtle_pretty`
+#[derive(Eq, PartialEq, Debug, Clone, Default)]
+pub struct TurtleWriterConfig {
+    /// The indentation of the predicates following the first one of a subject
+    pub indent: Indent,
+}
+
+/// Serializes the triples of the graph having `subject` as subject as a Turtle document
+///
+/// The prefixes are used to abbreviate the IRIs and only the used ones are declared.
+/// Returns an empty string if there are no such triples.
+pub fn subject_to_turtle(
+    graph: &Graph,
+    subject: &NamedOrBlankNode,
+    prefixes: &HashMap<String, String>,
+) -> String {
+    let mut triples: Vec<&Triple> = graph
+        .triples_mentioning(subject)
+        .filter(|triple| triple.subject() == subject)
+        .collect();
+    if triples.is_empty() {
+        return String::default();
+    }
+    triples.sort();
+    let mut formatter = TermFormatter::new(prefixes);
+    let mut block = formatter.subject(subject);
+    let mut current_predicate = None;
+    for triple in triples {
+        if current_predicate == Some(triple.predicate()) {
+            block.push_str(", ");
+        } else {
+            if current_predicate.is_some() {
+                block.push_str(" ;\n   ");
+            }
+            block.push(' ');
+            block.push_str(&formatter.named_node(triple.predicate()));
+            block.push(' ');
+            current_predicate = Some(triple.predicate());
+        }
+        block.push_str(&formatter.term(triple.object()));
+    }
+    block.push_str(" .\n");
+    let directives = formatter.prefix_directives();
+    if directives.is_empty() {
+        block
+    } else {
+        format!("{}\n{}", directives, block)
+    }
+}
+
+/// Writes the triples in Turtle grouping them by subject and predicate
+///
+/// The subjects are written in the order in which they first appear and their predicates are written on their own lines using `;`.
+/// The objects sharing the same subject and predicate are separated by `,`.
+/// Only the `prefixes` used by the triples are declared.
+pub fn write_turtle_pretty<'a, W: Write>(
+    triples: impl IntoIterator<Item = &'a Triple>,
+    prefixes: &HashMap<String, String>,
+    config: &TurtleWriterConfig,
+    mut sink: W,
+) -> RioResult<()> {
+    // Group triples by subject and predicate, keeping the order in which they first appear
+    let mut blocks: Vec<(&NamedOrBlankNode, PredicateObjects)> = Vec::default();
+    let mut block_by_subject: HashMap<&NamedOrBlankNode, usize> = HashMap::default();
+    for triple in triples {
+        let position = *block_by_subject.entry(triple.subject()).or_insert_with(|| {
+            blocks.push((triple.subject(), Vec::default()));
+            blocks.len() - 1
+        });
+        let predicates = &mut blocks[position].1;
+        match predicates
+            .iter_mut()
+            .find(|(predicate, _)| *predicate == triple.predicate())
+        {
+            Some((_, objects)) => objects.push(triple.object()),
+            None => predicates.push((triple.predicate(), vec![triple.object()])),
+        }
+    }
+
+    let mut formatter = TermFormatter::new(prefixes);
+    let mut body = String::default();
+    for (subject, predicates) in blocks {
+        body.push('\n');
+        body.push_str(&formatter.subject(subject));
+        for (i, (predicate, objects)) in predicates.into_iter().enumerate() {
+            if i > 0 {
+                body.push_str(&format!(" ;\n{}", config.indent));
+            } else {
+                body.push(' ');
+            }
+            body.push_str(&formatter.named_node(predicate));
+            for (j, object) in objects.into_iter().enumerate() {
+                body.push_str(if j > 0 { ", " } else { " " });
+                body.push_str(&formatter.term(object));
+            }
+        }
+        body.push_str(" .\n");
+    }
+    let directives = formatter.prefix_directives();
+    sink.write_all(directives.as_bytes())?;
+    sink.write_all(
+        if directives.is_empty() {
+            body.trim_start_matches('\n')
+        } else {
+            &body
+        }
+        .as_bytes(),
+    )?;
+    sink.flush()?;
+    Ok(())
+}
+
+/// The objects of a subject grouped by predicate
+type PredicateObjects<'a> = Vec<(&'a NamedNode, Vec<&'a Term>)>;
+
+/// The prefixes used for the well known namespaces by `suggest_prefixes`
+const WELL_KNOWN_PREFIXES: &[(&str, &str)] = &[
+    ("rdf", "http://www.w3.org/1999/02/22-rdf-syntax-ns#"),
+    ("rdfs", "http://www.w3.org/2000/01/rdf-schema#"),
+    ("xsd", "http://www.w3.org/2001/XMLSchema#"),
+    ("owl", "http://www.w3.org/2002/07/owl#"),
+];
+
+/// Builds a prefix map of at most `max_prefixes` prefixes covering as many of the IRIs as possible
+///
+/// The namespace of an IRI is the IRI up to its last `#` or `/`, like in `Graph::namespaces`, and the most common namespaces are chosen first.
+/// The IRIs that could not be written as prefixed names are ignored.
+/// The well known namespaces get their usual prefix like `rdf` or `xsd`, the other ones are named after their last path segment or `ns1`, `ns2`...
+pub fn suggest_prefixes<'a>(
+    iris: impl IntoIterator<Item = &'a str>,
+    max_prefixes: usize,
+) -> HashMap<String, String> {
+    let mut counts: HashMap<&str, usize> = HashMap::default();
+    for iri in iris {
+        if let Some(i) = iri.rfind(['#', '/']) {
+            if is_valid_local_name(&iri[i + 1..]) {
+                *counts.entry(&iri[..=i]).or_default() += 1;
+            }
+        }
+    }
+    let mut namespaces: Vec<(&str, usize)> = counts.into_iter().collect();
+    namespaces.sort_by(|(n1, c1), (n2, c2)| c2.cmp(c1).then(n1.cmp(n2)));
+
+    let mut prefixes = HashMap::default();
+    for (namespace, _) in namespaces.into_iter().take(max_prefixes) {
+        let well_known = WELL_KNOWN_PREFIXES
+            .iter()
+            .find(|(_, iri)| *iri == namespace)
+            .map(|(prefix, _)| (*prefix).to_owned());
+        let segment = namespace
+            .trim_end_matches(['#', '/'])
+            .rsplit(['/', '#', ':'])
+            .next()
+            .unwrap_or_default()
+            .to_ascii_lowercase();
+        let prefix = well_known
+            .into_iter()
+            .chain(Some(segment))
+            .filter(|prefix| !prefix.is_empty() && is_valid_prefix(prefix))
+            .chain((1..).map(|i| format!("ns{}", i)))
+            .find(|prefix| !prefixes.contains_key(prefix))
+            .unwrap();
+        prefixes.insert(prefix, namespace.to_owned());
+    }
+    prefixes
+}
+
+/// Formats terms in Turtle, using prefixed names when a prefix allows it
+pub(crate) struct TermFormatter<'a> {
+    prefixes: &'a HashMap<String, String>,
+    /// The prefixes used by the formatted terms
+    pub used_prefixes: BTreeMap<&'a str, &'a str>,
+}
+
+impl<'a> TermFormatter<'a> {
+    pub fn new(prefixes: &'a HashMap<String, String>) -> Self {
+        TermFormatter {
+            prefixes,
+            used_prefixes: BTreeMap::default(),
+        }
+    }
+
+    /// Formats the `@prefix` directives of the used prefixes, ordered by prefix
+    pub fn prefix_directives(&self) -> String {
+        self.used_prefixes
+            .iter()
+            .map(|(prefix, iri)| prefix_directive(prefix, iri))
+            .collect()
+    }
+
+    pub fn named_node(&mut self, node: &NamedNode) -> String {
+        let iri = node.value();
+        let best_prefix = self
+            .prefixes
+            .iter()
+            .filter(|(prefix, namespace)| {
+                is_valid_prefix(prefix)
+                    && iri.starts_with(namespace.as_str())
+                    && is_valid_local_name(&iri[namespace.len()..])
+            })
+            .max_by_key(|(prefix, namespace)| (namespace.len(), prefix.as_str()));
+        match best_prefix {
+            Some((prefix, namespace)) => {
+                self.used_prefixes.insert(prefix, namespace);
+                format!("{}:{}", prefix, &iri[namespace.len()..])
+            }
+            None => format!("<{}>", escape_iri(iri)),
+        }
+    }
+
+    pub fn subject(&mut self, node: &NamedOrBlankNode) -> String {
+        match node {
+            NamedOrBlankNode::NamedNode(node) => self.named_node(node),
+            NamedOrBlankNode::BlankNode(node) => node.to_string(),
+        }
+    }
+
+    pub fn term(&mut self, term: &Term) -> String {
+        match term {
+            Term::NamedNode(node) => self.named_node(node),
+            Term::BlankNode(node) => node.to_string(),
+            Term::Literal(Literal::SimpleLiteral(value)) => {
+                format!("\"{}\"", escape_literal(value))
+            }
+            Term::Literal(Literal::LanguageTaggedString { value, language }) => {
+                format!("\"{}\"@{}", escape_literal(value), language)
+            }
+            Term::Literal(Literal::TypedLiteral { value, datatype }) => format!(
+                "\"{}\"^^{}",
+                escape_literal(value),
+                self.named_node(datatype)
+            ),
+        }
+    }
+}
+
+fn escape_iri(iri: &str) -> String {
+    escape_with(|buffer| ntriples::write_iri_content(buffer, iri, EscapeStyle::Utf8))
+}
+
+/// Runs one of the N-Triples escaping functions, that are shared by the Turtle writers
+fn escape_with(escape: impl FnOnce(&mut Vec<u8>) -> io::Result<()>) -> String {
+    let mut buffer = Vec::default();
+    escape(&mut buffer).expect("Writing to a Vec should not fail");
+    String::from_utf8(buffer).expect("The N-Triples escaping should output UTF-8")
+}
+
+/// Formats the `@prefix` directive declaring the prefix, followed by a new line
+fn prefix_directive(prefix: &str, iri: &str) -> String {
+    format!("@prefix {}: <{}> .\n", prefix, escape_iri(iri))
+}
+
+/// Checks if the prefix is a valid [PN_PREFIX](https://www.w3.org/TR/turtle/#grammar-production-PN_PREFIX), with a conservative set of characters
+fn is_valid_prefix(prefix: &str) -> bool {
+    prefix.is_empty()
+        || (prefix.starts_with(|c: char| c.is_ascii_alphabetic())
+            && !prefix.ends_with('.')
+            && prefix
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-' || c == '.'))
+}
+
+/// Checks if the name is a valid [PN_LOCAL](https://www.w3.org/TR/turtle/#grammar-production-PN_LOCAL) without escapes, with a conservative set of characters
+fn is_valid_local_name(name: &str) -> bool {
+    name.starts_with(|c: char| c.is_ascii_alphanumeric() || c == '_')
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '-')
+}
+
+/// A push parser for Turtle documents that are received by chunks, for example from the network
+///
+/// The statements are parsed as soon as they are complete, even if they are split between multiple chunks.
+/// Only the incomplete statement at the end of the received chunks is kept in memory.
+/// Syntax errors are only reported by `finish`.
+pub struct TurtleParser {
+    data_factory: DataFactory,
+    options: TurtleOptions,
+    state: ParserState,
+    buffer: Vec<u8>,
+    /// The position of the buffer start in the document
+    offset: usize,
+    /// The number of lines before the buffer start
+    line: usize,
+    /// The number of characters between the last line start and the buffer start
+    column: usize,
+    /// The position in the buffer up to which the statement ends have been looked for
+    scanned: usize,
+    /// The lexical context at the `scanned` position
+    lexical_state: LexicalState,
+    /// The nesting level of the brackets and parentheses at the `scanned` position
+    depth: usize,
+    /// If a statement is invalid. The parsing stops and the error is reported by `finish`.
+    failed: bool,
+}
+
+/// The lexical contexts in which a `.` does not end a statement
+#[derive(Eq, PartialEq, Debug, Clone, Copy)]
+enum LexicalState {
+    Default,
+    Iri,
+    Comment,
+    String { quote: u8, long: bool },
+}
+
+impl TurtleParser {
+    pub fn new(data_factory: &DataFactory) -> Self {
+        TurtleParser::with_options(data_factory, TurtleOptions::default())
+    }
+
+    pub fn with_options(data_factory: &DataFactory, options: TurtleOptions) -> Self {
+        TurtleParser {
+            data_factory: data_factory.clone(),
+            options,
+            state: ParserState::default(),
+            buffer: Vec::default(),
+            offset: 0,
+            line: 0,
+            column: 0,
+            scanned: 0,
+            lexical_state: LexicalState::Default,
+            depth: 0,
+            failed: false,
+        }
+    }
+
+    /// Adds a chunk of the document and returns the triples of the statements completed by it
+    pub fn push_chunk(&mut self, bytes: &[u8]) -> Vec<Triple> {
+        self.buffer.extend_from_slice(bytes);
+        if self.failed {
+            return Vec::default();
+        }
+        let end = match self.scan_statement_ends() {
+            Some(end) => end,
+            None => return Vec::default(),
+        };
+        // Only the new complete statements are parsed
+        let mut state = self.state.clone();
+        let mut triples = Vec::default();
+        let is_valid = match str::from_utf8(&self.buffer[..end]) {
+            Ok(text) => grammar::turtleDoc(
+                text,
+                &mut state,
+                &mut triples,
+                &self.data_factory,
+                &self.options,
+            )
+            .is_ok(),
+            Err(_) => false,
+        };
+        if !is_valid {
+            self.failed = true;
+            return Vec::default();
+        }
+        state.located_error = None;
+        self.state = state;
+        for c in String::from_utf8_lossy(&self.buffer[..end]).chars() {
+            if c == '\n' {
+                self.line += 1;
+                self.column = 0;
+            } else {
+                self.column += 1;
+            }
+        }
+        self.buffer.drain(..end);
+        self.offset += end;
+        self.scanned -= end;
+        triples
+    }
+
+    /// Looks for the ends of statements in the part of the buffer not scanned yet and returns the position after the last one
+    ///
+    /// A statement ends with a `.` followed by a whitespace or a comment, outside of IRIs, strings, comments and brackets.
+    /// The scan stops before a character if the next ones are needed to know its meaning.
+    fn scan_statement_ends(&mut self) -> Option<usize> {
+        let bytes = &self.buffer;
+        let mut last_end = None;
+        let mut i = self.scanned;
+        while i < bytes.len() {
+            let c = bytes[i];
+            match self.lexical_state {
+                LexicalState::Comment => {
+                    if c == b'\n' || c == b'\r' {
+                        self.lexical_state = LexicalState::Default;
+                    }
+                }
+                LexicalState::Iri => {
+                    if c == b'>' {
+                        self.lexical_state = LexicalState::Default;
+                    }
+                }
+                LexicalState::String { quote, long } => {
+                    if c == b'\\' {
+                        if i + 1 >= bytes.len() {
+                            break;
+                        }
+                        i += 1; // The escaped character
+                    } else if c == quote && !long {
+                        self.lexical_state = LexicalState::Default;
+                    } else if c == quote {
+                        match bytes.get(i + 1..i + 3) {
+                            Some(next) if next == [quote, quote] => {
+                                i += 2;
+                                self.lexical_state = LexicalState::Default;
+                            }
+                            Some(_) => (),
+                            None => break,
+                        }
+                    }
+                }
+                LexicalState::Default => match c {
+                    b'#' => self.lexical_state = LexicalState::Comment,
+                    b'<' => self.lexical_state = LexicalState::Iri,
+                    b'"' | b'\'' => match bytes.get(i + 1..i + 3) {
+                        Some(next) if next == [c, c] => {
+                            i += 2;
+                            self.lexical_state = LexicalState::String {
+                                quote: c,
+                                long: true,
+                            };
+                        }
+                        Some(_) => {
+                            self.lexical_state = LexicalState::String {
+                                quote: c,
+                                long: false,
+                            }
+                        }
+                        None => break,
+                    },
+                    b'\\' => {
+                        if i + 1 >= bytes.len() {
+                            break;
+                        }
+                        i += 1; // An escape in a local name
+                    }
+                    b'[' | b'(' | b'{' => self.depth += 1,
+                    b']' | b')' | b'}' => self.depth = self.depth.saturating_sub(1),
+                    b'.' if self.depth == 0 => match bytes.get(i + 1) {
+                        // Otherwise the dot is part of a number or a local name
+                        Some(next) if next.is_ascii_whitespace() || *next == b'#' => {
+                            last_end = Some(i + 1)
+                        }
+                        Some(_) => (),
+                        None => break,
+                    },
+                    _ => (),
+                },
+            }
+            i += 1;
+        }
+        self.scanned = i;
+        last_end
+    }
+
+    /// Ends the document and returns the triples of its last statements
+    pub fn finish(mut self) -> RioResult<Vec<Triple>> {
+        let text = str::from_utf8(&self.buffer).map_err(RioError::new)?;
+        let mut triples = Vec::default();
+        grammar::turtleDoc(
+            text,
+            &mut self.state,
+            &mut triples,
+            &self.data_factory,
+            &self.options,
+        )
+        .map_err(|mut error| {
+            // The grammar positions are relative to the buffer start
+            if error.line == 1 {
+                error.column += self.column;
+            }
+            error.line += self.line;
+            syntax_error(error, self.state.clone(), self.offset)
+        })?;
+        Ok(triples)
     }
 }
@@ -0,0 +1,113 @@
+extern crate rudf;
+
+use rudf::model::data::*;
+use rudf::model::dataset::*;
+use std::iter::FromIterator;
+
+#[test]
+fn test_diff() {
+    let data_factory = DataFactory::default();
+    let s = data_factory.named_node("http://example.com/s");
+    let p = data_factory.named_node("http://example.com/p");
+    let g1: NamedOrBlankNode = data_factory.named_node("http://example.com/g1").into();
+    let g2: NamedOrBlankNode = data_factory.named_node("http://example.com/g2").into();
+    let quad = |value: &str, graph_name: &Option<NamedOrBlankNode>| {
+        data_factory.quad(
+            s.clone(),
+            p.clone(),
+            data_factory.simple_literal(value),
+            graph_name.clone(),
+        )
+    };
+    let old: Dataset = vec![
+        quad("default", &None),
+        quad("a", &Some(g1.clone())),
+        quad("b", &Some(g1.clone())),
+        quad("c", &Some(g2.clone())),
+    ]
+    .into_iter()
+    .collect();
+    let new: Dataset = vec![
+        quad("default", &None),
+        quad("a", &Some(g1.clone())),
+        quad("d", &Some(g1.clone())),
+        quad("c", &Some(g2.clone())),
+    ]
+    .into_iter()
+    .collect();
+
+    let diff = old.diff(&new);
+    assert_eq!(diff.graphs.len(), 1);
+    assert_eq!(
+        diff.graphs[&Some(g1.clone())],
+        GraphDiff {
+            added: vec![quad("d", &Some(g1.clone()))],
+            removed: vec![quad("b", &Some(g1.clone()))],
+        }
+    );
+    assert!(old.diff(&old).is_empty());
+
+    // The default graph is compared separately from the named graphs
+    let moved: Dataset = vec![quad("default", &Some(g2.clone()))]
+        .into_iter()
+        .collect();
+    let diff = Dataset::from_iter(vec![quad("default", &None)]).diff(&moved);
+    assert_eq!(diff.graphs[&None].removed, vec![quad("default", &None)]);
+    assert_eq!(
+        diff.graphs[&Some(g2.clone())].added,
+        vec![quad("default", &Some(g2))]
+    );
+}
+
+#[test]
+fn test_to_merged_graph() {
+    let data_factory = DataFactory::default();
+    let s = data_factory.named_node("http://example.com/s");
+    let p = data_factory.named_node("http://example.com/p");
+    let b = data_factory.blank_node("b");
+    let g1: NamedOrBlankNode = data_factory.named_node("http://example.com/g1").into();
+    let g2: NamedOrBlankNode = data_factory.named_node("http://example.com/g2").into();
+    let shared = data_factory.triple(s.clone(), p.clone(), b.clone());
+    let only_default = data_factory.triple(b.clone(), p.clone(), s.clone());
+    let dataset: Dataset = vec![
+        shared.clone().in_graph(&None),
+        shared.clone().in_graph(&Some(g1.clone())),
+        shared.clone().in_graph(&Some(g2)),
+        only_default.clone().in_graph(&None),
+    ]
+    .into_iter()
+    .collect();
+
+    let graph = dataset.to_merged_graph();
+    assert_eq!(graph.len(), 2);
+    assert!(graph.contains(&shared));
+    assert!(graph.contains(&only_default));
+    assert!(Dataset::default().to_merged_graph().is_empty());
+}
+
+#[test]
+fn test_blank_node_graphs() {
+    let data_factory = DataFactory::default();
+    let s = data_factory.named_node("http://example.com/s");
+    let p = data_factory.named_node("http://example.com/p");
+    let b = data_factory.blank_node("b");
+    let g: NamedOrBlankNode = data_factory.named_node("http://example.com/g").into();
+    let triple = data_factory.triple(s.clone(), p.clone(), b.clone());
+    let mut dataset: Dataset = vec![
+        triple.clone().in_graph(&None),
+        triple.clone().in_graph(&Some(g.clone())),
+        triple.clone().in_graph(&Some(b.clone().into())),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(dataset.find_blank_node_graphs(), vec![&b]);
+
+    dataset.skolemize_graph_names("http://example.com/");
+    assert!(dataset.find_blank_node_graphs().is_empty());
+    assert_eq!(dataset.len(), 3);
+    let skolem_graph: NamedOrBlankNode = data_factory
+        .named_node("http://example.com/.well-known/genid/b")
+        .into();
+    assert!(dataset.contains(&triple.clone().in_graph(&Some(skolem_graph))));
+    assert!(dataset.contains(&triple.in_graph(&None)));
+}
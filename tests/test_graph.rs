@@ -0,0 +1,1058 @@
+extern crate rudf;
+
+use rudf::model::data::*;
+use rudf::model::graph::*;
+use rudf::model::shapes::*;
+use rudf::rio::turtle::read_turtle;
+use std::collections::HashSet;
+
+fn example_triples(data_factory: &DataFactory) -> Vec<Triple> {
+    let s = data_factory.named_node("http://example.com/s");
+    let p = data_factory.named_node("http://example.com/p");
+    vec![
+        data_factory.triple(s.clone(), p.clone(), data_factory.simple_literal("foo")),
+        data_factory.triple(s.clone(), p.clone(), data_factory.blank_node("b1")),
+        data_factory.triple(data_factory.blank_node("b1"), p, s),
+    ]
+}
+
+#[test]
+fn test_ground_eq() {
+    let data_factory = DataFactory::default();
+    let triples = example_triples(&data_factory);
+    let graph: Graph = triples.iter().cloned().collect();
+    let reversed_graph: Graph = triples.iter().rev().cloned().collect();
+    assert!(graph.ground_eq(&reversed_graph));
+
+    let mut smaller_graph = graph.clone();
+    assert!(smaller_graph.remove(&triples[0]));
+    assert!(!graph.ground_eq(&smaller_graph));
+}
+
+#[test]
+fn test_ground_eq_compares_blank_node_ids() {
+    let data_factory = DataFactory::default();
+    let p = data_factory.named_node("http://example.com/p");
+    let o = data_factory.named_node("http://example.com/o");
+    let graph: Graph =
+        vec![data_factory.triple(data_factory.blank_node("b1"), p.clone(), o.clone())]
+            .into_iter()
+            .collect();
+    let renamed_graph: Graph = vec![data_factory.triple(data_factory.blank_node("b2"), p, o)]
+        .into_iter()
+        .collect();
+    assert!(!graph.ground_eq(&renamed_graph));
+}
+
+#[test]
+fn test_map_iris() {
+    let data_factory = DataFactory::default();
+    let mut graph = Graph::default();
+    graph.insert(data_factory.triple(
+        data_factory.named_node("http://old.example.com/s"),
+        data_factory.named_node("http://example.com/p"),
+        data_factory.named_node("http://old.example.com/o"),
+    ));
+    graph.insert(data_factory.triple(
+        data_factory.named_node("http://old.example.com/s"),
+        data_factory.named_node("http://example.com/p"),
+        data_factory.typed_literal("foo", data_factory.named_node("http://old.example.com/dt")),
+    ));
+
+    graph.map_iris(|iri| {
+        if iri.starts_with("http://old.example.com/") {
+            Some(iri.replacen("http://old.example.com/", "http://new.example.com/", 1))
+        } else {
+            None
+        }
+    });
+
+    let expected: Graph = vec![
+        data_factory.triple(
+            data_factory.named_node("http://new.example.com/s"),
+            data_factory.named_node("http://example.com/p"),
+            data_factory.named_node("http://new.example.com/o"),
+        ),
+        data_factory.triple(
+            data_factory.named_node("http://new.example.com/s"),
+            data_factory.named_node("http://example.com/p"),
+            data_factory.typed_literal("foo", data_factory.named_node("http://new.example.com/dt")),
+        ),
+    ]
+    .into_iter()
+    .collect();
+    assert!(graph.ground_eq(&expected));
+}
+
+#[test]
+fn test_extend() {
+    let data_factory = DataFactory::default();
+    let triples = example_triples(&data_factory);
+    let mut graph = Graph::with_capacity(triples.len());
+    assert!(graph.is_empty());
+    graph.extend(triples.iter().cloned());
+    graph.extend(triples.iter().cloned());
+    assert_eq!(graph.len(), triples.len());
+    for triple in &triples {
+        assert!(graph.contains(triple));
+    }
+}
+
+#[test]
+fn test_iter_sorted() {
+    let data_factory = DataFactory::default();
+    let triples = example_triples(&data_factory);
+    let graph: Graph = triples.iter().cloned().collect();
+    let reversed_graph: Graph = triples.iter().rev().cloned().collect();
+    let sorted: Vec<&Triple> = graph.iter_sorted().collect();
+    assert_eq!(sorted, reversed_graph.iter_sorted().collect::<Vec<_>>());
+    assert_eq!(sorted.len(), triples.len());
+    for window in sorted.windows(2) {
+        assert!(window[0] < window[1]);
+    }
+}
+
+#[test]
+fn test_predicate_map() {
+    let data_factory = DataFactory::default();
+    let s1 = NamedOrBlankNode::from(data_factory.named_node("http://example.com/s1"));
+    let s2 = NamedOrBlankNode::from(data_factory.blank_node("s2"));
+    let label = data_factory.named_node("http://www.w3.org/2000/01/rdf-schema#label");
+    let graph: Graph = vec![
+        data_factory.triple(s1.clone(), label.clone(), data_factory.simple_literal("a")),
+        data_factory.triple(
+            s1.clone(),
+            label.clone(),
+            data_factory.language_tagged_literal("b", "en"),
+        ),
+        data_factory.triple(s2.clone(), label.clone(), data_factory.simple_literal("c")),
+        data_factory.triple(
+            s2.clone(),
+            data_factory.named_node("http://example.com/p"),
+            data_factory.simple_literal("d"),
+        ),
+    ]
+    .into_iter()
+    .collect();
+
+    let map = graph.predicate_map(&label);
+    assert_eq!(map.len(), 2);
+    let mut s1_labels = map[&s1].clone();
+    s1_labels.sort();
+    assert_eq!(
+        s1_labels,
+        vec![
+            Term::from(data_factory.simple_literal("a")),
+            Term::from(data_factory.language_tagged_literal("b", "en")),
+        ]
+    );
+    assert_eq!(map[&s2], vec![Term::from(data_factory.simple_literal("c"))]);
+}
+
+#[test]
+fn test_blank_nodes() {
+    let data_factory = DataFactory::default();
+    let mut graph: Graph = example_triples(&data_factory).into_iter().collect();
+    graph.insert(data_factory.triple(
+        data_factory.blank_node("b2"),
+        data_factory.named_node("http://example.com/p"),
+        data_factory.simple_literal("b3"),
+    ));
+    let mut blank_nodes: Vec<&BlankNode> = graph.blank_nodes().collect();
+    blank_nodes.sort();
+    assert_eq!(
+        blank_nodes,
+        vec![
+            &data_factory.blank_node("b1"),
+            &data_factory.blank_node("b2")
+        ]
+    );
+}
+
+#[test]
+fn test_validate_shapes() {
+    let data_factory = DataFactory::default();
+    let rdf_type = data_factory.named_node("http://www.w3.org/1999/02/22-rdf-syntax-ns#type");
+    let label = data_factory.named_node("http://www.w3.org/2000/01/rdf-schema#label");
+    let person = data_factory.named_node("http://example.com/Person");
+    let alice = data_factory.named_node("http://example.com/alice");
+    let bob = data_factory.named_node("http://example.com/bob");
+    let graph: Graph = vec![
+        data_factory.triple(alice.clone(), rdf_type.clone(), person.clone()),
+        data_factory.triple(
+            alice.clone(),
+            label.clone(),
+            data_factory.simple_literal("Alice"),
+        ),
+        data_factory.triple(bob.clone(), rdf_type, person.clone()),
+    ]
+    .into_iter()
+    .collect();
+    let shapes = vec![Shape {
+        class: person,
+        properties: vec![PropertyConstraint {
+            predicate: label.clone(),
+            min_count: 1,
+            max_count: Some(1),
+            datatype: Some(data_factory.named_node("http://www.w3.org/2001/XMLSchema#string")),
+        }],
+    }];
+
+    assert_eq!(
+        graph.validate_shapes(&shapes),
+        vec![Violation {
+            focus_node: bob.into(),
+            predicate: label,
+            kind: ViolationKind::MinCount {
+                expected: 1,
+                actual: 0
+            },
+        }]
+    );
+}
+
+#[test]
+fn test_iter_by_subject() {
+    let data_factory = DataFactory::default();
+    let triples = example_triples(&data_factory);
+    let graph: Graph = triples.iter().cloned().collect();
+    let groups: Vec<(&NamedOrBlankNode, Vec<&Triple>)> = graph.iter_by_subject().collect();
+    assert_eq!(
+        groups,
+        vec![
+            (triples[0].subject(), vec![&triples[1], &triples[0]]),
+            (triples[2].subject(), vec![&triples[2]]),
+        ]
+    );
+}
+
+#[test]
+fn test_subclass_closure() {
+    let data_factory = DataFactory::default();
+    let sub_class_of = data_factory.named_node("http://www.w3.org/2000/01/rdf-schema#subClassOf");
+    let a = data_factory.named_node("http://example.com/A");
+    let b = data_factory.named_node("http://example.com/B");
+    let c = data_factory.named_node("http://example.com/C");
+    let mut graph: Graph = vec![
+        data_factory.triple(a.clone(), sub_class_of.clone(), b.clone()),
+        data_factory.triple(b.clone(), sub_class_of.clone(), c.clone()),
+    ]
+    .into_iter()
+    .collect();
+    graph.subclass_closure();
+    assert_eq!(graph.len(), 3);
+    assert!(graph.contains(&data_factory.triple(a.clone(), sub_class_of.clone(), c.clone())));
+
+    // Cycles should not loop forever
+    graph.insert(data_factory.triple(c.clone(), sub_class_of.clone(), a.clone()));
+    graph.subclass_closure();
+    assert_eq!(graph.len(), 9);
+    assert!(graph.contains(&data_factory.triple(a.clone(), sub_class_of, a)));
+}
+
+#[test]
+fn test_contains_pattern() {
+    let data_factory = DataFactory::default();
+    let s: NamedOrBlankNode = data_factory.named_node("http://example.com/s").into();
+    let other: NamedOrBlankNode = data_factory.named_node("http://example.com/other").into();
+    let p = data_factory.named_node("http://example.com/p");
+    let q = data_factory.named_node("http://example.com/q");
+    let foo: Term = data_factory.simple_literal("foo").into();
+    let mut graph: Graph = example_triples(&data_factory).into_iter().collect();
+
+    assert!(graph.contains_pattern(None, None, None));
+    assert!(graph.contains_pattern(Some(&s), None, None));
+    assert!(graph.contains_pattern(Some(&s), Some(&p), Some(&foo)));
+    assert!(graph.contains_pattern(None, None, Some(&foo)));
+    assert!(!graph.contains_pattern(Some(&other), None, None));
+    assert!(!graph.contains_pattern(Some(&s), Some(&q), None));
+    assert!(!graph.contains_pattern(None, Some(&q), None));
+
+    for triple in example_triples(&data_factory) {
+        graph.remove(&triple);
+    }
+    assert!(!graph.contains_pattern(Some(&s), None, None));
+    assert!(!graph.contains_pattern(None, None, None));
+}
+
+#[test]
+fn test_transactional_graph() {
+    let data_factory = DataFactory::default();
+    let triples = example_triples(&data_factory);
+    let new_triple = data_factory.triple(
+        data_factory.named_node("http://example.com/s2"),
+        data_factory.named_node("http://example.com/p"),
+        data_factory.simple_literal("bar"),
+    );
+    let initial: Graph = triples.iter().cloned().collect();
+    let mut graph = TransactionalGraph::new(initial.clone());
+
+    graph.begin();
+    graph.insert(new_triple.clone());
+    graph.remove(&triples[0]);
+    assert!(graph.is_in_transaction());
+    assert!(graph.graph().ground_eq(&initial));
+    graph.rollback();
+    assert!(!graph.is_in_transaction());
+    assert!(graph.graph().ground_eq(&initial));
+
+    graph.begin();
+    graph.insert(new_triple.clone());
+    graph.remove(&triples[0]);
+    graph.commit();
+    assert!(!graph.is_in_transaction());
+    assert!(graph.graph().contains(&new_triple));
+    assert!(!graph.graph().contains(&triples[0]));
+    assert_eq!(graph.graph().len(), triples.len());
+}
+
+#[test]
+fn test_predicates_of() {
+    let data_factory = DataFactory::default();
+    let s = data_factory.named_node("http://example.com/s");
+    let p = data_factory.named_node("http://example.com/p");
+    let q = data_factory.named_node("http://example.com/q");
+    let graph: Graph = vec![
+        data_factory.triple(s.clone(), p.clone(), data_factory.simple_literal("a")),
+        data_factory.triple(s.clone(), p.clone(), data_factory.simple_literal("b")),
+        data_factory.triple(s.clone(), q.clone(), data_factory.simple_literal("c")),
+        data_factory.triple(
+            data_factory.named_node("http://example.com/o"),
+            data_factory.named_node("http://example.com/r"),
+            s.clone(),
+        ),
+    ]
+    .into_iter()
+    .collect();
+
+    let mut predicates: Vec<&NamedNode> = graph.predicates_of(&s.clone().into()).collect();
+    predicates.sort();
+    assert_eq!(predicates, vec![&p, &q]);
+    assert_eq!(
+        graph
+            .predicates_of(&data_factory.named_node("http://example.com/p").into())
+            .count(),
+        0
+    );
+}
+
+#[test]
+fn test_index_stats() {
+    let data_factory = DataFactory::default();
+    let graph: Graph = example_triples(&data_factory).into_iter().collect();
+    let stats = graph.index_stats();
+    assert_eq!(stats.triple_count, graph.len());
+    assert_eq!(stats.subject_index_entries, 2);
+    assert!(stats.estimated_bytes > 0);
+    assert_eq!(Graph::default().index_stats().triple_count, 0);
+
+    // The subject index shares the term strings of the triple set, so they are only counted once
+    let long_iri = format!("http://example.com/{}", "a".repeat(10_000));
+    let graph: Graph = vec![data_factory.triple(
+        data_factory.named_node(long_iri.clone()),
+        data_factory.named_node("http://example.com/p"),
+        data_factory.named_node("http://example.com/o"),
+    )]
+    .into_iter()
+    .collect();
+    let estimated_bytes = graph.index_stats().estimated_bytes;
+    assert!(estimated_bytes > long_iri.len());
+    assert!(estimated_bytes < 2 * long_iri.len());
+}
+
+#[test]
+fn test_skolemize() {
+    let data_factory = DataFactory::default();
+    let original: Graph = example_triples(&data_factory).into_iter().collect();
+    let mut graph = original.clone();
+
+    graph.skolemize("http://example.com/");
+    assert_eq!(graph.len(), original.len());
+    assert_eq!(graph.blank_nodes().count(), 0);
+    let skolem_iri: Term = data_factory
+        .named_node("http://example.com/.well-known/genid/b1")
+        .into();
+    assert!(graph.iter().any(|t| *t.object() == skolem_iri));
+    assert!(graph
+        .iter()
+        .any(|t| Term::from(t.subject().clone()) == skolem_iri));
+
+    graph.deskolemize("http://example.com");
+    assert!(graph.ground_eq(&original));
+}
+
+#[test]
+fn test_merge() {
+    let data_factory = DataFactory::default();
+    let p = data_factory.named_node("http://example.com/p");
+    let b1 = data_factory.blank_node("b1");
+    let ground = data_factory.triple(
+        data_factory.named_node("http://example.com/s"),
+        p.clone(),
+        data_factory.simple_literal("ground"),
+    );
+    let mut graph: Graph = vec![
+        data_factory.triple(b1.clone(), p.clone(), data_factory.simple_literal("alice")),
+        ground.clone(),
+    ]
+    .into_iter()
+    .collect();
+    let other: Graph = vec![
+        data_factory.triple(b1.clone(), p.clone(), data_factory.simple_literal("bob")),
+        ground.clone(),
+    ]
+    .into_iter()
+    .collect();
+
+    graph.merge(other);
+    assert_eq!(graph.len(), 3);
+    assert_eq!(graph.blank_nodes().count(), 2);
+    let subject_of = |name: &str| {
+        let object = Term::from(data_factory.simple_literal(name));
+        graph
+            .iter()
+            .find(|t| *t.object() == object)
+            .unwrap()
+            .subject()
+            .clone()
+    };
+    assert_eq!(subject_of("alice"), b1.clone().into());
+    assert_ne!(subject_of("bob"), b1.into());
+}
+
+#[test]
+fn test_lists() {
+    let data_factory = DataFactory::default();
+    let first = data_factory.named_node("http://www.w3.org/1999/02/22-rdf-syntax-ns#first");
+    let rest = data_factory.named_node("http://www.w3.org/1999/02/22-rdf-syntax-ns#rest");
+    let nil = data_factory.named_node("http://www.w3.org/1999/02/22-rdf-syntax-ns#nil");
+    let l1 = data_factory.blank_node("l1");
+    let l2 = data_factory.blank_node("l2");
+    let a = data_factory.simple_literal("a");
+    let b = data_factory.simple_literal("b");
+    let mut graph: Graph = vec![
+        data_factory.triple(l1.clone(), first.clone(), a.clone()),
+        data_factory.triple(l1.clone(), rest.clone(), l2.clone()),
+        data_factory.triple(l2.clone(), first.clone(), b.clone()),
+        data_factory.triple(l2.clone(), rest.clone(), nil.clone()),
+    ]
+    .into_iter()
+    .collect();
+    assert!(graph.is_well_formed_list(&l1.clone().into()));
+    assert_eq!(
+        graph.list_items(&l1.clone().into()),
+        Some(vec![&Term::from(a), &Term::from(b)])
+    );
+    assert_eq!(graph.list_items(&nil.clone().into()), Some(Vec::default()));
+    assert!(!graph.is_well_formed_list(&data_factory.blank_node("other").into()));
+
+    // Cycle
+    graph.remove(&data_factory.triple(l2.clone(), rest.clone(), nil.clone()));
+    graph.insert(data_factory.triple(l2.clone(), rest.clone(), l1.clone()));
+    assert!(!graph.is_well_formed_list(&l1.clone().into()));
+    assert_eq!(graph.list_items(&l1.clone().into()), None);
+
+    // Multiple rdf:rest
+    graph.insert(data_factory.triple(l2.clone(), rest.clone(), nil.clone()));
+    assert!(!graph.is_well_formed_list(&l1.into()));
+}
+
+#[test]
+fn test_instances_of() {
+    let data_factory = DataFactory::default();
+    let rdf_type = data_factory.named_node("http://www.w3.org/1999/02/22-rdf-syntax-ns#type");
+    let person = data_factory.named_node("http://example.com/Person");
+    let agent = data_factory.named_node("http://example.com/Agent");
+    let alice: NamedOrBlankNode = data_factory.named_node("http://example.com/alice").into();
+    let bob: NamedOrBlankNode = data_factory.blank_node("bob").into();
+    let graph: Graph = vec![
+        data_factory.triple(alice.clone(), rdf_type.clone(), person.clone()),
+        data_factory.triple(alice.clone(), rdf_type.clone(), agent.clone()),
+        data_factory.triple(bob.clone(), rdf_type.clone(), person.clone()),
+        data_factory.triple(
+            bob.clone(),
+            data_factory.named_node("http://example.com/knows"),
+            agent.clone(),
+        ),
+    ]
+    .into_iter()
+    .collect();
+
+    let mut instances: Vec<&NamedOrBlankNode> = graph.instances_of(&person).collect();
+    instances.sort();
+    assert_eq!(instances, vec![&alice, &bob]);
+    assert_eq!(graph.instances_of(&agent).collect::<Vec<_>>(), vec![&alice]);
+
+    let mut types: Vec<&Term> = graph.types_of(&alice).collect();
+    types.sort();
+    assert_eq!(types, vec![&Term::from(agent), &Term::from(person)]);
+    assert_eq!(graph.types_of(&bob).count(), 1);
+}
+
+#[test]
+fn test_predicate_ranges() {
+    let data_factory = DataFactory::default();
+    let s = data_factory.named_node("http://example.com/s");
+    let mixed = data_factory.named_node("http://example.com/mixed");
+    let name = data_factory.named_node("http://example.com/name");
+    let integer = data_factory.named_node("http://www.w3.org/2001/XMLSchema#integer");
+    let graph: Graph = vec![
+        data_factory.triple(s.clone(), mixed.clone(), s.clone()),
+        data_factory.triple(s.clone(), mixed.clone(), data_factory.blank_node("b")),
+        data_factory.triple(
+            s.clone(),
+            mixed.clone(),
+            data_factory.typed_literal("1", integer.clone()),
+        ),
+        data_factory.triple(
+            s.clone(),
+            mixed.clone(),
+            data_factory.typed_literal("2", integer.clone()),
+        ),
+        data_factory.triple(s.clone(), mixed.clone(), data_factory.simple_literal("foo")),
+        data_factory.triple(s.clone(), name.clone(), data_factory.simple_literal("s")),
+    ]
+    .into_iter()
+    .collect();
+
+    let ranges = graph.predicate_ranges();
+    assert_eq!(ranges.len(), 2);
+    let mixed_range = &ranges[&mixed];
+    assert_eq!(mixed_range.named_nodes, 1);
+    assert_eq!(mixed_range.blank_nodes, 1);
+    assert_eq!(mixed_range.literals, 3);
+    assert_eq!(mixed_range.datatypes.len(), 2);
+    assert_eq!(mixed_range.datatypes[&integer], 2);
+    assert!(!mixed_range.is_object_valued());
+    assert!(!mixed_range.is_datatype_valued());
+    assert!(ranges[&name].is_datatype_valued());
+}
+
+#[test]
+fn test_iter_refs() {
+    let data_factory = DataFactory::default();
+    let triples = example_triples(&data_factory);
+    let graph: Graph = triples.iter().cloned().collect();
+    let mut refs: Vec<TripleRef> = graph.iter_refs().collect();
+    refs.sort();
+    let mut expected = triples.clone();
+    expected.sort();
+    assert_eq!(
+        refs,
+        expected.iter().map(TripleRef::from).collect::<Vec<_>>()
+    );
+    assert!(refs
+        .iter()
+        .all(|triple| graph.contains(&triple.into_owned())));
+}
+
+#[test]
+fn test_find_relative_iris() {
+    let data_factory = DataFactory::default();
+    let mut graph: Graph = example_triples(&data_factory).into_iter().collect();
+    assert!(graph.find_relative_iris().is_empty());
+
+    let relative = data_factory.named_node("foo/bar");
+    graph.insert(data_factory.triple(
+        data_factory.blank_node("b1"),
+        data_factory.named_node("http://example.com/p"),
+        relative.clone(),
+    ));
+    graph.insert(data_factory.triple(
+        relative.clone(),
+        data_factory.named_node("http://example.com/p"),
+        data_factory.simple_literal("foo"),
+    ));
+    assert_eq!(graph.find_relative_iris(), vec![&relative]);
+}
+
+#[test]
+fn test_insert_all() {
+    let data_factory = DataFactory::default();
+    let triples = example_triples(&data_factory);
+    let mut graph = Graph::default();
+    graph.insert(triples[1].clone());
+    assert_eq!(
+        graph.insert_all(vec![
+            triples[0].clone(),
+            triples[1].clone(),
+            triples[0].clone(),
+            triples[2].clone(),
+        ]),
+        vec![true, false, false, true]
+    );
+    assert_eq!(graph.len(), 3);
+    assert_eq!(graph.insert_all(Vec::default()), Vec::<bool>::default());
+}
+
+#[test]
+fn test_triples_mentioning() {
+    let data_factory = DataFactory::default();
+    let triples = example_triples(&data_factory);
+    let s: NamedOrBlankNode = data_factory.named_node("http://example.com/s").into();
+    let p = data_factory.named_node("http://example.com/p");
+    let mut graph: Graph = triples.iter().cloned().collect();
+    let self_loop = data_factory.triple(s.clone(), p.clone(), s.clone());
+    graph.insert(self_loop.clone());
+    graph.insert(data_factory.triple(
+        data_factory.named_node("http://example.com/o"),
+        p,
+        data_factory.simple_literal("foo"),
+    ));
+
+    let mut mentioning: Vec<&Triple> = graph.triples_mentioning(&s).collect();
+    mentioning.sort();
+    let mut expected = vec![&triples[0], &triples[1], &triples[2], &self_loop];
+    expected.sort();
+    assert_eq!(mentioning, expected);
+
+    let b1: NamedOrBlankNode = data_factory.blank_node("b1").into();
+    let mut mentioning: Vec<&Triple> = graph.triples_mentioning(&b1).collect();
+    mentioning.sort();
+    let mut expected = vec![&triples[1], &triples[2]];
+    expected.sort();
+    assert_eq!(mentioning, expected);
+
+    let unknown: NamedOrBlankNode = data_factory.blank_node("unknown").into();
+    assert_eq!(graph.triples_mentioning(&unknown).count(), 0);
+}
+
+#[test]
+fn test_filter_schema() {
+    let data_factory = DataFactory::default();
+    let graph: Graph = read_turtle(
+        "@prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+        @prefix owl: <http://www.w3.org/2002/07/owl#> .
+        @prefix ex: <http://example.com/> .
+        ex:Person a owl:Class ; rdfs:subClassOf ex:Agent .
+        ex:name a owl:DatatypeProperty ; rdfs:domain ex:Person .
+        ex:alice a ex:Person ; ex:name \"Alice\" ; rdfs:label \"Alice\" ."
+            .as_bytes(),
+        &data_factory,
+    )
+    .unwrap()
+    .collect();
+
+    let schema = graph.filter_schema();
+    let expected: Graph = read_turtle(
+        "@prefix rdfs: <http://www.w3.org/2000/01/rdf-schema#> .
+        @prefix owl: <http://www.w3.org/2002/07/owl#> .
+        @prefix ex: <http://example.com/> .
+        ex:Person a owl:Class ; rdfs:subClassOf ex:Agent .
+        ex:name a owl:DatatypeProperty ; rdfs:domain ex:Person ."
+            .as_bytes(),
+        &data_factory,
+    )
+    .unwrap()
+    .collect();
+    assert!(schema.ground_eq(&expected));
+
+    let mut filter = SchemaFilter::default();
+    filter
+        .predicates
+        .insert("http://www.w3.org/2000/01/rdf-schema#label".to_owned());
+    filter.classes.clear();
+    assert_eq!(graph.filter_schema_with(&filter).len(), 3);
+}
+
+#[test]
+fn test_is_functional() {
+    let data_factory = DataFactory::default();
+    let p = data_factory.named_node("http://example.com/p");
+    let q = data_factory.named_node("http://example.com/q");
+    let mut graph: Graph = example_triples(&data_factory).into_iter().collect();
+    assert!(!graph.is_functional(&p));
+    assert_eq!(graph.max_cardinality(&p), 2);
+
+    graph.insert(data_factory.triple(
+        data_factory.named_node("http://example.com/s"),
+        q.clone(),
+        data_factory.simple_literal("a"),
+    ));
+    graph.insert(data_factory.triple(
+        data_factory.blank_node("b1"),
+        q.clone(),
+        data_factory.simple_literal("a"),
+    ));
+    assert!(graph.is_functional(&q));
+    assert_eq!(graph.max_cardinality(&q), 1);
+
+    let unused = data_factory.named_node("http://example.com/unused");
+    assert!(graph.is_functional(&unused));
+    assert_eq!(graph.max_cardinality(&unused), 0);
+}
+
+#[test]
+fn test_namespaces() {
+    let data_factory = DataFactory::default();
+    let graph: Graph = vec![
+        data_factory.triple(
+            data_factory.named_node("http://example.com/s"),
+            data_factory.named_node("http://xmlns.com/foaf/0.1/name"),
+            data_factory.blank_node("b"),
+        ),
+        data_factory.triple(
+            data_factory.blank_node("b"),
+            data_factory.named_node("http://example.com/p"),
+            data_factory.simple_literal("foo"),
+        ),
+    ]
+    .into_iter()
+    .collect();
+    let namespaces = graph.namespaces();
+    assert_eq!(namespaces.len(), 3);
+    assert!(namespaces.contains("http://example.com/"));
+    assert!(namespaces.contains("http://xmlns.com/foaf/0.1/"));
+    assert!(namespaces.contains("http://www.w3.org/2001/XMLSchema#"));
+
+    let mut graph = Graph::default();
+    graph.insert(data_factory.triple(
+        data_factory.named_node("urn:isbn:123"),
+        data_factory.named_node("http://www.w3.org/1999/02/22-rdf-syntax-ns#type"),
+        data_factory.named_node("urn:book"),
+    ));
+    assert_eq!(
+        graph.namespaces().into_iter().collect::<Vec<_>>(),
+        vec!["http://www.w3.org/1999/02/22-rdf-syntax-ns#".to_owned()]
+    );
+}
+
+#[test]
+fn test_connected_components() {
+    let data_factory = DataFactory::default();
+    let graph: Graph = read_turtle(
+        "@prefix ex: <http://example.com/> .
+        ex:a ex:p ex:a1, ex:a2, \"a\" .
+        ex:a3 ex:p ex:a .
+        ex:b ex:p ex:b1, _:b, \"a\" .
+        _:b ex:p ex:b2 ."
+            .as_bytes(),
+        &data_factory,
+    )
+    .unwrap()
+    .collect();
+
+    let components = graph.connected_components();
+    assert_eq!(components.len(), 2);
+    assert_eq!(components[0].len(), 4);
+    assert_eq!(components[1].len(), 4);
+    let a: NamedOrBlankNode = data_factory.named_node("http://example.com/a").into();
+    let b: NamedOrBlankNode = data_factory.named_node("http://example.com/b").into();
+    assert_eq!(components[0].triples_mentioning(&a).count(), 4);
+    assert_eq!(components[1].triples_mentioning(&b).count(), 3);
+    assert_eq!(
+        components.iter().map(Graph::len).sum::<usize>(),
+        graph.len()
+    );
+    assert!(Graph::default().connected_components().is_empty());
+}
+
+#[test]
+fn test_reified_statements() {
+    let data_factory = DataFactory::default();
+    let graph: Graph = read_turtle(
+        "@prefix rdf: <http://www.w3.org/1999/02/22-rdf-syntax-ns#> .
+        @prefix ex: <http://example.com/> .
+        ex:statement a rdf:Statement ;
+            rdf:subject ex:s ; rdf:predicate ex:p ; rdf:object \"o\" ;
+            ex:source ex:wikipedia .
+        ex:untyped rdf:subject _:b ; rdf:predicate ex:p ; rdf:object ex:o .
+        ex:incomplete a rdf:Statement ; rdf:subject ex:s ; rdf:predicate ex:p .
+        ex:ambiguous rdf:subject ex:s ; rdf:predicate ex:p ; rdf:object ex:o, ex:o2 .
+        ex:literal rdf:subject \"s\" ; rdf:predicate ex:p ; rdf:object ex:o ."
+            .as_bytes(),
+        &data_factory,
+    )
+    .unwrap()
+    .collect();
+
+    let statements: Vec<(NamedOrBlankNode, Triple)> = graph.reified_statements().collect();
+    assert_eq!(statements.len(), 2);
+    assert_eq!(
+        statements[0],
+        (
+            data_factory
+                .named_node("http://example.com/statement")
+                .into(),
+            data_factory.triple(
+                data_factory.named_node("http://example.com/s"),
+                data_factory.named_node("http://example.com/p"),
+                data_factory.simple_literal("o")
+            )
+        )
+    );
+    assert_eq!(
+        statements[1].0,
+        data_factory.named_node("http://example.com/untyped").into()
+    );
+    assert_eq!(statements[1].1.subject().kind(), TermKind::BlankNode);
+    assert_eq!(
+        statements[1].1.object(),
+        &data_factory.named_node("http://example.com/o").into()
+    );
+}
+
+#[test]
+fn test_reify() {
+    let data_factory = DataFactory::default();
+    let triple = example_triples(&data_factory)[0].clone();
+    let (statement, triples) = data_factory.reify(&triple);
+    assert_eq!(triples.len(), 4);
+    assert!(triples
+        .iter()
+        .all(|t| *t.subject() == NamedOrBlankNode::from(statement.clone())));
+
+    let graph: Graph = triples.into_iter().collect();
+    assert!(!graph.contains(&triple));
+    assert_eq!(
+        graph.reified_statements().collect::<Vec<_>>(),
+        vec![(statement.into(), triple)]
+    );
+}
+
+#[test]
+fn test_degrees() {
+    let data_factory = DataFactory::default();
+    let graph: Graph = read_turtle(
+        "@prefix ex: <http://example.com/> .
+        ex:hub ex:p ex:a, ex:b, ex:c, \"literal\" .
+        ex:a ex:p ex:hub .
+        ex:b ex:p ex:b ."
+            .as_bytes(),
+        &data_factory,
+    )
+    .unwrap()
+    .collect();
+    let node = |name: &str| -> NamedOrBlankNode {
+        data_factory
+            .named_node(format!("http://example.com/{}", name))
+            .into()
+    };
+
+    assert_eq!(graph.out_degree(&node("hub")), 4);
+    assert_eq!(graph.in_degree(&node("hub")), 1);
+    assert_eq!(graph.out_degree(&node("b")), 1);
+    assert_eq!(graph.in_degree(&node("b")), 2);
+    assert_eq!(graph.out_degree(&node("c")), 0);
+    assert_eq!(graph.in_degree(&node("c")), 1);
+    assert_eq!(graph.out_degree(&node("unknown")), 0);
+    assert_eq!(graph.in_degree(&node("unknown")), 0);
+
+    let histogram: Vec<(usize, usize)> = graph.degree_histogram().into_iter().collect();
+    // c: 1, a: 2, b: 3, hub: 5
+    assert_eq!(histogram, vec![(1, 1), (2, 1), (3, 1), (5, 1)]);
+}
+
+#[test]
+fn test_triples_with_object_value() {
+    let data_factory = DataFactory::default();
+    let graph: Graph = read_turtle(
+        "@prefix ex: <http://example.com/> .
+        ex:s ex:p \"http://example.com/o\", \"http://example.com/o\"@en, <http://example.com/o> .
+        ex:s ex:q \"other\" ."
+            .as_bytes(),
+        &data_factory,
+    )
+    .unwrap()
+    .collect();
+
+    let mut objects: Vec<String> = graph
+        .triples_with_object_value("http://example.com/o")
+        .map(|triple| triple.object().to_string())
+        .collect();
+    objects.sort();
+    assert_eq!(
+        objects,
+        vec![
+            "\"http://example.com/o\"",
+            "\"http://example.com/o\"@en",
+            "<http://example.com/o>"
+        ]
+    );
+    assert_eq!(graph.triples_with_object_value("unknown").count(), 0);
+}
+
+#[test]
+fn test_batches() {
+    let data_factory = DataFactory::default();
+    let graph: Graph = (0..10)
+        .map(|i| {
+            data_factory.triple(
+                data_factory.named_node("http://example.com/s"),
+                data_factory.named_node("http://example.com/p"),
+                data_factory.simple_literal(i.to_string()),
+            )
+        })
+        .collect();
+
+    let batches: Vec<_> = graph.batches(4).collect();
+    assert_eq!(
+        batches.iter().map(Vec::len).collect::<Vec<_>>(),
+        vec![4, 4, 2]
+    );
+    let triples: HashSet<&Triple> = batches.into_iter().flatten().collect();
+    assert_eq!(triples, graph.iter().collect());
+    assert_eq!(Graph::default().batches(4).count(), 0);
+}
+
+#[test]
+fn test_to_adjacency() {
+    let data_factory = DataFactory::default();
+    let graph: Graph = example_triples(&data_factory).into_iter().collect();
+    let s: NamedOrBlankNode = data_factory.named_node("http://example.com/s").into();
+    let p = data_factory.named_node("http://example.com/p");
+
+    let adjacency = graph.to_adjacency();
+    assert_eq!(adjacency.len(), 2);
+    assert_eq!(
+        adjacency[&s],
+        vec![
+            (p.clone(), data_factory.blank_node("b1").into()),
+            (p.clone(), data_factory.simple_literal("foo").into()),
+        ]
+    );
+    assert_eq!(
+        adjacency[&data_factory.blank_node("b1").into()],
+        vec![(p, s.into())]
+    );
+}
+
+#[test]
+fn test_remove_self_loops() {
+    let data_factory = DataFactory::default();
+    let mut graph: Graph = read_turtle(
+        "@prefix ex: <http://example.com/> .
+        @prefix owl: <http://www.w3.org/2002/07/owl#> .
+        ex:x owl:sameAs ex:x, ex:y .
+        ex:x ex:label \"http://example.com/x\" ."
+            .as_bytes(),
+        &data_factory,
+    )
+    .unwrap()
+    .collect();
+
+    assert_eq!(graph.remove_self_loops(), 1);
+    assert_eq!(graph.len(), 2);
+    assert!(!graph.contains(&data_factory.triple(
+        data_factory.named_node("http://example.com/x"),
+        data_factory.named_node("http://www.w3.org/2002/07/owl#sameAs"),
+        data_factory.named_node("http://example.com/x"),
+    )));
+    assert_eq!(graph.remove_self_loops(), 0);
+}
+
+#[test]
+fn test_find_unknown_datatypes() {
+    let data_factory = DataFactory::default();
+    let graph: Graph = read_turtle(
+        "@prefix ex: <http://example.com/> .
+        @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+        ex:s ex:p 1, \"2\"^^xsd:integr, \"3\"^^xsd:integr, \"foo\", \"bar\"@en, ex:o ."
+            .as_bytes(),
+        &data_factory,
+    )
+    .unwrap()
+    .collect();
+    let xsd =
+        |name: &str| data_factory.named_node(format!("http://www.w3.org/2001/XMLSchema#{}", name));
+    let lang_string =
+        data_factory.named_node("http://www.w3.org/1999/02/22-rdf-syntax-ns#langString");
+
+    let known: HashSet<NamedNode> = vec![xsd("integer"), xsd("string"), lang_string.clone()]
+        .into_iter()
+        .collect();
+    assert_eq!(graph.find_unknown_datatypes(&known), vec![&xsd("integr")]);
+
+    let known: HashSet<NamedNode> = vec![xsd("integer")].into_iter().collect();
+    assert_eq!(
+        graph.find_unknown_datatypes(&known),
+        vec![&lang_string, &xsd("integr"), &xsd("string")]
+    );
+}
+
+#[test]
+fn test_datatypes_used() {
+    let data_factory = DataFactory::default();
+    let graph: Graph = read_turtle(
+        "@prefix ex: <http://example.com/> .
+        ex:s ex:p 1, 2, \"foo\", ex:o ."
+            .as_bytes(),
+        &data_factory,
+    )
+    .unwrap()
+    .collect();
+
+    let expected: HashSet<NamedNode> = vec![
+        data_factory.named_node("http://www.w3.org/2001/XMLSchema#integer"),
+        data_factory.named_node("http://www.w3.org/2001/XMLSchema#string"),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(graph.datatypes_used(), expected);
+    assert!(Graph::default().datatypes_used().is_empty());
+}
+
+#[test]
+fn test_relabel_blank_nodes_deterministically() {
+    let data_factory = DataFactory::default();
+    let file = "@prefix ex: <http://example.com/> .
+        _:b ex:name \"b\" ; ex:knows _:a, _:c .
+        _:a ex:name \"a\" ; ex:knows _:a .
+        _:c ex:knows ex:d .";
+    let load = || -> Graph {
+        read_turtle(file.as_bytes(), &data_factory)
+            .unwrap()
+            .collect()
+    };
+
+    let mut first = load();
+    first.relabel_blank_nodes_deterministically();
+    let mut second = load();
+    second.relabel_blank_nodes_deterministically();
+    assert_eq!(
+        first.iter_sorted().collect::<Vec<_>>(),
+        second.iter_sorted().collect::<Vec<_>>()
+    );
+
+    let mut labels: Vec<&str> = first.blank_nodes().map(BlankNode::value).collect();
+    labels.sort();
+    assert_eq!(labels, vec!["b0", "b1", "b2"]);
+    assert_eq!(first.len(), load().len());
+
+    // Relabeling again does not change the labels
+    let mut third = first.clone();
+    third.relabel_blank_nodes_deterministically();
+    assert!(third.ground_eq(&first));
+
+    // The nodes of a cycle all have the same neighborhood so their former labels decide their order
+    let next = data_factory.named_node("http://example.com/next");
+    let mut cycle: Graph = (0..12)
+        .map(|i| {
+            data_factory.triple(
+                data_factory.blank_node(format!("n{}", i)),
+                next.clone(),
+                data_factory.blank_node(format!("n{}", (i + 1) % 12)),
+            )
+        })
+        .collect();
+    cycle.relabel_blank_nodes_deterministically();
+    let expected: Graph = (0..12)
+        .map(|i| {
+            data_factory.triple(
+                data_factory.blank_node(format!("b{}", i)),
+                next.clone(),
+                data_factory.blank_node(format!("b{}", (i + 1) % 12)),
+            )
+        })
+        .collect();
+    assert_eq!(
+        cycle.iter_sorted().collect::<Vec<_>>(),
+        expected.iter_sorted().collect::<Vec<_>>()
+    );
+    let mut relabeled_twice = cycle.clone();
+    relabeled_twice.relabel_blank_nodes_deterministically();
+    assert_eq!(
+        relabeled_twice.iter_sorted().collect::<Vec<_>>(),
+        cycle.iter_sorted().collect::<Vec<_>>()
+    );
+}
@@ -0,0 +1,1014 @@
+extern crate rudf;
+
+use rudf::model::data::*;
+use rudf::model::datatypes::*;
+use rudf::model::iri::*;
+use rudf::model::rdfjs::*;
+use rudf::model::xsd::*;
+use std::borrow::Cow;
+use std::cmp::Ordering;
+use std::collections::hash_map::DefaultHasher;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::hash::Hash;
+use std::hash::Hasher;
+use std::time::Duration;
+
+/// Checks that the blank node id only uses characters allowed in N-Triples and Turtle blank node labels
+fn assert_valid_blank_node(node: &BlankNode) {
+    assert!(!node.value().is_empty());
+    assert!(
+        node.value()
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-'),
+        "invalid blank node label {}",
+        node
+    );
+}
+
+#[test]
+fn test_prefixed_blank_node_ids() {
+    let data_factory = DataFactory::with_blank_node_prefix("n");
+    let first = data_factory.new_blank_node();
+    let second = data_factory.new_blank_node();
+    assert_eq!(first.value(), "n1");
+    assert_eq!(second.value(), "n2");
+    assert_valid_blank_node(&first);
+    assert_valid_blank_node(&second);
+}
+
+#[test]
+fn test_uuid_blank_node_ids() {
+    let data_factory = DataFactory::with_uuid_blank_nodes();
+    let first = data_factory.new_blank_node();
+    let second = data_factory.new_blank_node();
+    assert_ne!(first, second);
+    assert_eq!(first.value().len(), 36);
+    assert_valid_blank_node(&first);
+    assert_valid_blank_node(&second);
+}
+
+#[test]
+fn test_typed_literal_checked() {
+    let data_factory = DataFactory::default();
+    assert_eq!(
+        data_factory.typed_literal_checked(
+            "x",
+            data_factory.named_node("http://www.w3.org/1999/02/22-rdf-syntax-ns#langString")
+        ),
+        Err(LiteralError::LangStringWithoutLanguage)
+    );
+    let integer = data_factory.named_node("http://www.w3.org/2001/XMLSchema#integer");
+    assert_eq!(
+        data_factory.typed_literal_checked("1", integer.clone()),
+        Ok(data_factory.typed_literal("1", integer))
+    );
+}
+
+#[test]
+fn test_language_tagged_literal_checked() {
+    let data_factory = DataFactory::default();
+    assert_eq!(
+        data_factory.language_tagged_literal_checked("color", "en-US"),
+        Ok(data_factory.language_tagged_literal("color", "en-US"))
+    );
+    assert!(data_factory
+        .language_tagged_literal_checked("Farbe", "de-1996")
+        .is_ok());
+    for invalid in &["en_US", "123", "", "en-", "-US", "en--US", "fr-é"] {
+        assert_eq!(
+            data_factory.language_tagged_literal_checked("x", *invalid),
+            Err(LiteralError::InvalidLanguageTag(invalid.to_string()))
+        );
+    }
+}
+
+#[test]
+fn test_into_quads() {
+    let data_factory = DataFactory::default();
+    let s = data_factory.named_node("http://example.com/s");
+    let p = data_factory.named_node("http://example.com/p");
+    let g = NamedOrBlankNode::from(data_factory.named_node("http://example.com/g"));
+    let triples = vec![
+        data_factory.triple(s.clone(), p.clone(), data_factory.simple_literal("1")),
+        data_factory.triple(s.clone(), p.clone(), data_factory.simple_literal("2")),
+    ];
+    let quads: Vec<Quad> = triples.into_iter().into_quads(g.clone()).collect();
+    assert_eq!(
+        quads,
+        vec![
+            data_factory.quad(
+                s.clone(),
+                p.clone(),
+                data_factory.simple_literal("1"),
+                g.clone()
+            ),
+            data_factory.quad(s, p, data_factory.simple_literal("2"), g),
+        ]
+    );
+}
+
+#[test]
+fn test_term_as_predicate() {
+    let data_factory = DataFactory::default();
+    let iri = data_factory.named_node("http://example.com/p");
+    let literal = Term::from(data_factory.simple_literal("p"));
+    let blank_node = Term::from(data_factory.blank_node("p"));
+
+    assert_eq!(Term::from(iri.clone()).as_predicate(), Some(&iri));
+    assert_eq!(Term::from(iri.clone()).into_predicate(), Ok(iri));
+    assert_eq!(literal.as_predicate(), None);
+    assert_eq!(literal.clone().into_predicate(), Err(literal));
+    assert_eq!(blank_node.as_predicate(), None);
+    assert_eq!(blank_node.clone().into_predicate(), Err(blank_node));
+}
+
+#[test]
+fn test_binary_literals() {
+    let data_factory = DataFactory::default();
+    let bytes: &[u8] = b"\x00\xffrudf!";
+    for encoding in &[BinaryEncoding::Hex, BinaryEncoding::Base64] {
+        for length in 0..bytes.len() {
+            let literal = data_factory.binary_literal(&bytes[..length], *encoding);
+            assert_eq!(literal.datatype().value(), encoding.datatype());
+            assert_eq!(literal.as_bytes(), Some(bytes[..length].to_vec()));
+        }
+    }
+    assert_eq!(
+        data_factory
+            .binary_literal(b"rudf", BinaryEncoding::Base64)
+            .value(),
+        "cnVkZg=="
+    );
+    assert_eq!(
+        data_factory
+            .binary_literal(b"\xab", BinaryEncoding::Hex)
+            .value(),
+        "AB"
+    );
+}
+
+#[test]
+fn test_invalid_binary_literals() {
+    let data_factory = DataFactory::default();
+    let hex = data_factory.named_node("http://www.w3.org/2001/XMLSchema#hexBinary");
+    let base64 = data_factory.named_node("http://www.w3.org/2001/XMLSchema#base64Binary");
+    assert_eq!(
+        data_factory.typed_literal("ABC", hex.clone()).as_bytes(),
+        None
+    );
+    assert_eq!(data_factory.typed_literal("AG", hex).as_bytes(), None);
+    assert_eq!(
+        data_factory
+            .typed_literal("cnVkZg=", base64.clone())
+            .as_bytes(),
+        None
+    );
+    assert_eq!(
+        data_factory.typed_literal("cn=kZg==", base64).as_bytes(),
+        None
+    );
+    assert_eq!(data_factory.simple_literal("AB").as_bytes(), None);
+}
+
+#[test]
+fn test_resolve_named_node() {
+    let data_factory = DataFactory::default();
+    let base = data_factory.named_node("http://example.com/a/b/c?q");
+    let resolve = |reference: &str| {
+        data_factory
+            .resolve_named_node(reference, &base)
+            .map(|node| node.value().to_owned())
+    };
+    assert_eq!(resolve("../x"), Ok("http://example.com/a/x".to_owned()));
+    assert_eq!(
+        resolve("#frag"),
+        Ok("http://example.com/a/b/c?q#frag".to_owned())
+    );
+    assert_eq!(resolve("d"), Ok("http://example.com/a/b/d".to_owned()));
+    assert_eq!(resolve("./"), Ok("http://example.com/a/b/".to_owned()));
+    assert_eq!(resolve("/d/./e"), Ok("http://example.com/d/e".to_owned()));
+    assert_eq!(resolve("../../../d"), Ok("http://example.com/d".to_owned()));
+    assert_eq!(resolve("?y"), Ok("http://example.com/a/b/c?y".to_owned()));
+    assert_eq!(
+        resolve("//other.com/x"),
+        Ok("http://other.com/x".to_owned())
+    );
+    assert_eq!(resolve("urn:x:y"), Ok("urn:x:y".to_owned()));
+    assert_eq!(resolve("é/../f"), Ok("http://example.com/a/b/f".to_owned()));
+    assert_eq!(
+        data_factory
+            .resolve_named_node("urn:ébc", &data_factory.named_node("urn:x"))
+            .map(|node| node.value().to_owned()),
+        Ok("urn:ébc".to_owned())
+    );
+    assert!(resolve("a b").is_err());
+    assert_eq!(
+        data_factory.resolve_named_node("x", &data_factory.named_node("/relative")),
+        Err(IriError::MissingScheme("/relative".to_owned()))
+    );
+}
+
+#[test]
+fn test_triple_from_tuple() {
+    let data_factory = DataFactory::default();
+    let s = data_factory.named_node("http://example.com/s");
+    let p = data_factory.named_node("http://example.com/p");
+    let o = data_factory.simple_literal("o");
+    assert_eq!(
+        Triple::from((s.clone(), p.clone(), o.clone())),
+        data_factory.triple(s.clone(), p.clone(), o.clone())
+    );
+    assert_eq!(
+        Triple::from((
+            NamedOrBlankNode::from(s.clone()),
+            p.clone(),
+            Term::from(o.clone())
+        )),
+        data_factory.triple(s, p, o)
+    );
+}
+
+#[test]
+fn test_duration_literals() {
+    let data_factory = DataFactory::default();
+    let duration = |value: &str| {
+        data_factory
+            .typed_literal(
+                value,
+                data_factory.named_node("http://www.w3.org/2001/XMLSchema#duration"),
+            )
+            .as_duration()
+    };
+    let parsed = duration("PT1H30M").unwrap();
+    assert!(!parsed.is_negative());
+    assert_eq!(parsed.months(), 0);
+    assert_eq!(parsed.to_std_duration(), Some(Duration::from_secs(5400)));
+
+    let parsed = duration("-P1Y2M3DT0.25S").unwrap();
+    assert!(parsed.is_negative());
+    assert_eq!(parsed.months(), 14);
+    assert_eq!(parsed.day_time(), Duration::new(3 * 86400, 250_000_000));
+    assert_eq!(parsed.to_std_duration(), None);
+
+    for invalid in &[
+        "", "P", "PT", "P1DT", "1H", "P1H", "P1M1Y", "PT1.5M", "P-1D", "PT.S",
+    ] {
+        assert_eq!(duration(invalid), None, "{} should be invalid", invalid);
+    }
+    assert_eq!(data_factory.simple_literal("PT1H").as_duration(), None);
+}
+
+#[test]
+fn test_prefixed_iri_resolver() {
+    let mut resolver = PrefixedIriResolver::new(
+        Some("http://example.com/a/b".to_owned()),
+        HashMap::default(),
+    )
+    .unwrap();
+    resolver.add_prefix("ex", "http://example.org/ns#");
+    resolver.add_prefix("", "http://example.com/default/");
+
+    assert_eq!(
+        resolver.resolve("c"),
+        Ok("http://example.com/a/c".to_owned())
+    );
+    assert_eq!(
+        resolver.resolve("../c"),
+        Ok("http://example.com/c".to_owned())
+    );
+    assert_eq!(
+        resolver.resolve_prefixed_name("ex", "foo"),
+        Ok("http://example.org/ns#foo".to_owned())
+    );
+    assert_eq!(
+        resolver.resolve_prefixed_name("", "foo"),
+        Ok("http://example.com/default/foo".to_owned())
+    );
+    assert_eq!(
+        resolver.resolve_prefixed_name("other", "foo"),
+        Err(IriError::UnknownPrefix("other".to_owned()))
+    );
+    assert_eq!(
+        resolver.resolve("http://other.com/x"),
+        Ok("http://other.com/x".to_owned())
+    );
+    assert!(resolver.resolve("ex:a b").is_err());
+
+    // A prefix named like a scheme does not change the absolute IRIs
+    resolver.add_prefix("http", "http://example.org/hijacked#");
+    assert_eq!(
+        resolver.resolve("http://other.com/x"),
+        Ok("http://other.com/x".to_owned())
+    );
+    resolver.set_base("../d/").unwrap();
+    assert_eq!(resolver.base(), Some("http://example.com/d/"));
+
+    let resolver = PrefixedIriResolver::new(None, HashMap::default()).unwrap();
+    assert_eq!(
+        resolver.resolve("relative"),
+        Err(IriError::MissingScheme("relative".to_owned()))
+    );
+    assert!(BaseIriResolver::new("relative").is_err());
+    assert_eq!(
+        BaseIriResolver::new("http://example.com/a")
+            .unwrap()
+            .resolve_prefixed_name("ex", "foo"),
+        Err(IriError::UnknownPrefix("ex".to_owned()))
+    );
+    assert_eq!(
+        BaseIriResolver::new("http://example.com/a")
+            .unwrap()
+            .resolve("#b"),
+        Ok("http://example.com/a#b".to_owned())
+    );
+}
+
+#[test]
+fn test_term_at() {
+    let data_factory = DataFactory::default();
+    let s = data_factory.named_node("http://example.com/s");
+    let p = data_factory.named_node("http://example.com/p");
+    let o = data_factory.simple_literal("o");
+    let g = data_factory.blank_node("g");
+    let triple = data_factory.triple(s.clone(), p.clone(), o.clone());
+    assert_eq!(triple.term_at(0), Some(s.clone().into()));
+    assert_eq!(triple.term_at(1), Some(p.clone().into()));
+    assert_eq!(triple.term_at(2), Some(o.clone().into()));
+    assert_eq!(triple.term_at(3), None);
+
+    let quad = data_factory.quad(s.clone(), p.clone(), o.clone(), Some(g.clone().into()));
+    assert_eq!(quad.term_at(0), Some(s.clone().into()));
+    assert_eq!(quad.term_at(1), Some(p.clone().into()));
+    assert_eq!(quad.term_at(2), Some(o.clone().into()));
+    assert_eq!(quad.term_at(3), Some(g.into()));
+    assert_eq!(quad.term_at(4), None);
+    assert_eq!(data_factory.quad(s, p, o, None).term_at(3), None);
+}
+
+fn hash_of(value: &impl Hash) -> u64 {
+    let mut hasher = DefaultHasher::new();
+    value.hash(&mut hasher);
+    hasher.finish()
+}
+
+#[test]
+fn test_shared_storage() {
+    let data_factory = DataFactory::default();
+    let node = data_factory.named_node("http://example.com/a");
+    let clone = node.clone();
+    // The clones share their IRI
+    assert_eq!(clone.value().as_ptr(), node.value().as_ptr());
+    let other = data_factory.named_node("http://example.com/a");
+    assert_ne!(other.value().as_ptr(), node.value().as_ptr());
+    assert_eq!(other, node);
+    assert_eq!(hash_of(&other), hash_of(&node));
+    assert_eq!(hash_of(&node), hash_of(&"http://example.com/a"));
+    assert!(node < data_factory.named_node("http://example.com/b"));
+    let set: HashSet<NamedNode> = vec![node, clone, other].into_iter().collect();
+    assert_eq!(set.len(), 1);
+
+    let blank_node = data_factory.blank_node("b");
+    assert_eq!(
+        blank_node.clone().value().as_ptr(),
+        blank_node.value().as_ptr()
+    );
+    assert_eq!(blank_node, data_factory.blank_node("b"));
+    assert_eq!(hash_of(&blank_node), hash_of(&data_factory.blank_node("b")));
+}
+
+#[test]
+fn test_literal_interning() {
+    let data_factory = DataFactory::default().with_literal_interning();
+    let first = data_factory.simple_literal("a");
+    let second = data_factory.simple_literal("a".to_owned());
+    // The equal values share their storage
+    assert_eq!(second.value().as_ptr(), first.value().as_ptr());
+    assert_eq!(first, second);
+    assert_eq!(hash_of(&first), hash_of(&second));
+    let typed = data_factory.typed_literal("a", data_factory.named_node("http://example.com/d"));
+    let tagged = data_factory.language_tagged_literal("a", "en");
+    assert_eq!(typed.value().as_ptr(), first.value().as_ptr());
+    assert_eq!(tagged.value().as_ptr(), first.value().as_ptr());
+    assert_ne!(Term::from(typed), Term::from(first.clone()));
+    assert_ne!(
+        data_factory.simple_literal("b").value().as_ptr(),
+        first.value().as_ptr()
+    );
+    let clone = data_factory.clone();
+    assert_eq!(
+        clone.simple_literal("a").value().as_ptr(),
+        first.value().as_ptr()
+    );
+
+    let data_factory = DataFactory::default();
+    let other = data_factory.simple_literal("a");
+    assert_ne!(other.value().as_ptr(), first.value().as_ptr());
+    assert_eq!(other, first);
+    assert_eq!(hash_of(&other), hash_of(&first));
+}
+
+#[test]
+fn test_predicate_interning() {
+    let data_factory = DataFactory::default().with_predicate_interning();
+    let s = data_factory.named_node("http://example.com/s");
+    let o = data_factory.named_node("http://example.com/o");
+    let p = data_factory.named_node("http://example.com/p");
+    let first = data_factory.triple(
+        s.clone(),
+        data_factory.named_node("http://example.com/p"),
+        o.clone(),
+    );
+    let second = data_factory.triple(s.clone(), p.clone(), o.clone());
+    let quad = data_factory.quad(s.clone(), p.clone(), o.clone(), None);
+    // The predicates share the IRI of the first one
+    assert_eq!(
+        second.predicate().value().as_ptr(),
+        first.predicate().value().as_ptr()
+    );
+    assert_eq!(
+        quad.predicate().value().as_ptr(),
+        first.predicate().value().as_ptr()
+    );
+    assert_eq!(first, second);
+    assert_eq!(hash_of(&first), hash_of(&second));
+    assert_eq!(*second.predicate(), p);
+    let other = data_factory.triple(
+        s.clone(),
+        data_factory.named_node("http://example.com/q"),
+        o.clone(),
+    );
+    assert_ne!(
+        other.predicate().value().as_ptr(),
+        first.predicate().value().as_ptr()
+    );
+
+    // The clones of the factory share the interned IRIs
+    let clone = data_factory.clone();
+    let third = clone.triple(
+        s.clone(),
+        clone.named_node("http://example.com/p"),
+        o.clone(),
+    );
+    assert_eq!(
+        third.predicate().value().as_ptr(),
+        first.predicate().value().as_ptr()
+    );
+
+    let data_factory = DataFactory::default();
+    let first = data_factory.triple(
+        s.clone(),
+        data_factory.named_node("http://example.com/p"),
+        o.clone(),
+    );
+    let second = data_factory.triple(s, data_factory.named_node("http://example.com/p"), o);
+    assert_ne!(
+        second.predicate().value().as_ptr(),
+        first.predicate().value().as_ptr()
+    );
+    assert_eq!(first, second);
+}
+
+#[derive(Debug, PartialEq)]
+struct Point {
+    x: f64,
+    y: f64,
+}
+
+#[test]
+fn test_custom_datatypes() {
+    let data_factory = DataFactory::default();
+    let wkt = data_factory.named_node("http://www.opengis.net/ont/geosparql#wktLiteral");
+    let mut registry = DatatypeRegistry::default();
+    registry.register(&wkt, |value| {
+        let coordinates = value.trim().strip_prefix("POINT(")?.strip_suffix(')')?;
+        let mut coordinates = coordinates.split_whitespace().map(|c| c.parse().ok());
+        let point = Point {
+            x: coordinates.next()??,
+            y: coordinates.next()??,
+        };
+        if coordinates.next().is_some() {
+            None
+        } else {
+            Some(point)
+        }
+    });
+    assert!(registry.contains(&wkt));
+
+    let point = data_factory.typed_literal("POINT(1 2.5)", wkt.clone());
+    assert_eq!(
+        point.as_custom::<Point>(&registry),
+        Some(Point { x: 1., y: 2.5 })
+    );
+    assert_eq!(point.as_custom::<String>(&registry), None);
+    assert_eq!(
+        data_factory
+            .typed_literal("LINE(1 2)", wkt)
+            .as_custom::<Point>(&registry),
+        None
+    );
+    assert_eq!(
+        data_factory
+            .simple_literal("POINT(1 2)")
+            .as_custom::<Point>(&registry),
+        None
+    );
+}
+
+#[test]
+fn test_canonical_literals() {
+    let data_factory = DataFactory::default();
+    let decimal = |value: &str| {
+        data_factory.typed_literal(
+            value,
+            data_factory.named_node("http://www.w3.org/2001/XMLSchema#decimal"),
+        )
+    };
+    let integer = |value: &str| {
+        data_factory.typed_literal(
+            value,
+            data_factory.named_node("http://www.w3.org/2001/XMLSchema#int"),
+        )
+    };
+    assert_eq!(decimal("1.50").canonical(), decimal("1.5").canonical());
+    assert_eq!(decimal("1.50").canonical(), decimal("1.5"));
+    assert_eq!(decimal("+001.500").canonical(), decimal("1.5"));
+    assert_eq!(decimal("-.50").canonical(), decimal("-0.5"));
+    assert_eq!(decimal("2.00").canonical(), decimal("2"));
+    assert_eq!(decimal("-0.0").canonical(), decimal("0"));
+    assert_eq!(decimal("1.2.3").canonical(), decimal("1.2.3"));
+    assert_eq!(decimal(".").canonical(), decimal("."));
+    assert_eq!(integer("+0042").canonical(), integer("42"));
+    assert_eq!(integer("-00").canonical(), integer("0"));
+    assert_eq!(integer("1.0").canonical(), integer("1.0"));
+
+    let string = data_factory.simple_literal("1.50");
+    assert_eq!(string.canonical(), string);
+    let tagged = data_factory.language_tagged_literal("1.50", "en");
+    assert_eq!(tagged.canonical(), tagged);
+}
+
+#[test]
+fn test_canonical_literal_wrapper() {
+    let data_factory = DataFactory::default();
+    let decimal = data_factory.named_node("http://www.w3.org/2001/XMLSchema#decimal");
+    let first = CanonicalLiteral::new(data_factory.typed_literal("01.50", decimal.clone()));
+    let second = CanonicalLiteral::from(data_factory.typed_literal("1.5", decimal.clone()));
+
+    assert_eq!(
+        first.to_string(),
+        "\"01.50\"^^<http://www.w3.org/2001/XMLSchema#decimal>"
+    );
+    assert_eq!(first.original().value(), "01.50");
+    assert_eq!(first.canonical().value(), "1.5");
+    assert_ne!(first, second);
+    assert!(first.value_eq(&second));
+    assert!(!first.value_eq(&CanonicalLiteral::new(
+        data_factory.typed_literal("1.51", decimal.clone())
+    )));
+    assert_eq!(
+        first.into_original(),
+        data_factory.typed_literal("01.50", decimal)
+    );
+}
+
+#[test]
+fn test_triple_ref() {
+    let data_factory = DataFactory::default();
+    let subject: NamedOrBlankNode = data_factory.named_node("http://example.com/s").into();
+    let predicate = data_factory.named_node("http://example.com/p");
+    let object: Term = data_factory.simple_literal("foo").into();
+    let triple = data_factory.triple(subject.clone(), predicate.clone(), object.clone());
+
+    let triple_ref = TripleRef::new(&subject, &predicate, &object);
+    assert_eq!(triple_ref, triple);
+    assert_eq!(TripleRef::from(&triple), triple_ref);
+    assert_eq!(triple_ref.subject(), triple.subject());
+    assert_eq!(triple_ref.to_string(), triple.to_string());
+    assert_eq!(triple_ref.into_owned(), triple);
+    assert_eq!(Triple::from(triple_ref), triple);
+
+    let other_object: Term = data_factory.simple_literal("bar").into();
+    assert_ne!(TripleRef::new(&subject, &predicate, &other_object), triple);
+}
+
+#[test]
+fn test_language_ranges() {
+    let data_factory = DataFactory::default();
+    let en_us = data_factory.language_tagged_literal("color", "en-US");
+    assert!(en_us.matches_language_range("en"));
+    assert!(en_us.matches_language_range("EN-us"));
+    assert!(en_us.matches_language_range("*"));
+    assert!(!en_us.matches_language_range("fr"));
+    assert!(!en_us.matches_language_range("en-GB"));
+    assert!(!en_us.matches_language_range("e"));
+    assert!(!data_factory
+        .language_tagged_literal("foo", "eng")
+        .matches_language_range("en"));
+    assert!(!data_factory
+        .simple_literal("foo")
+        .matches_language_range("*"));
+    assert!(!data_factory
+        .typed_literal(
+            "1",
+            data_factory.named_node("http://www.w3.org/2001/XMLSchema#integer")
+        )
+        .matches_language_range("en"));
+
+    let de_latn_de = data_factory.language_tagged_literal("Farbe", "de-Latn-DE");
+    assert!(de_latn_de.matches_extended_language_range("de-*-DE"));
+    assert!(de_latn_de.matches_extended_language_range("de-DE"));
+    assert!(de_latn_de.matches_extended_language_range("*-DE"));
+    assert!(de_latn_de.matches_extended_language_range("de"));
+    assert!(!de_latn_de.matches_language_range("de-DE"));
+    assert!(!de_latn_de.matches_extended_language_range("fr-*"));
+    assert!(!de_latn_de.matches_extended_language_range("de-Latn-DE-1996"));
+    assert!(!data_factory
+        .language_tagged_literal("Farbe", "de-x-DE")
+        .matches_extended_language_range("de-DE"));
+    assert!(!data_factory
+        .simple_literal("foo")
+        .matches_extended_language_range("*"));
+}
+
+#[test]
+fn test_decoded_value() {
+    let data_factory = DataFactory::default();
+    let node = data_factory.named_node("http://x/a%20b");
+    assert_eq!(node.decoded_value(), "http://x/a b");
+    assert_eq!(node.value(), "http://x/a%20b");
+    assert_eq!(
+        data_factory
+            .named_node("http://x/%C3%A9t%c3%a9")
+            .decoded_value(),
+        "http://x/été"
+    );
+    assert_eq!(
+        data_factory.named_node("http://x/100%25%").decoded_value(),
+        "http://x/100%%"
+    );
+    assert_eq!(
+        data_factory.named_node("http://x/%zz%4").decoded_value(),
+        "http://x/%zz%4"
+    );
+    assert_eq!(
+        data_factory.named_node("http://x/%FF").decoded_value(),
+        "http://x/\u{FFFD}"
+    );
+    assert!(
+        match data_factory.named_node("http://x/a").decoded_value() {
+            Cow::Borrowed(value) => value == "http://x/a",
+            Cow::Owned(_) => false,
+        }
+    );
+}
+
+#[test]
+fn test_triple_fingerprint() {
+    let data_factory = DataFactory::default();
+    let s = data_factory.named_node("http://example.com/s");
+    let p = data_factory.named_node("http://example.com/p");
+    let triple = data_factory.triple(s.clone(), p.clone(), data_factory.simple_literal("foo"));
+    assert_eq!(triple.fingerprint(), triple.clone().fingerprint());
+    assert_eq!(
+        triple.fingerprint(),
+        0x8648_b7e4_92ac_cfac_618e_64aa_6ec8_09d6
+    );
+
+    let others = [
+        data_factory.triple(s.clone(), p.clone(), data_factory.simple_literal("bar")),
+        data_factory.triple(
+            s.clone(),
+            p.clone(),
+            data_factory.language_tagged_literal("foo", "en"),
+        ),
+        data_factory.triple(
+            s.clone(),
+            p.clone(),
+            data_factory.typed_literal("foo", data_factory.named_node("http://example.com/d")),
+        ),
+        data_factory.triple(s.clone(), p.clone(), data_factory.named_node("foo")),
+        data_factory.triple(s.clone(), p.clone(), data_factory.blank_node("foo")),
+        data_factory.triple(
+            data_factory.blank_node("http://example.com/s"),
+            p.clone(),
+            data_factory.simple_literal("foo"),
+        ),
+    ];
+    let fingerprints: HashSet<u128> = others
+        .iter()
+        .chain(Some(&triple))
+        .map(|triple| triple.fingerprint())
+        .collect();
+    assert_eq!(fingerprints.len(), others.len() + 1);
+}
+
+#[test]
+fn test_from_rdfjs() {
+    let data_factory = DataFactory::default();
+    assert_eq!(
+        data_factory.from_rdfjs(&RdfJsTerm::named_node("http://example.com/s")),
+        Ok(data_factory.named_node("http://example.com/s").into())
+    );
+    assert_eq!(
+        data_factory.from_rdfjs(&RdfJsTerm::blank_node("b")),
+        Ok(data_factory.blank_node("b").into())
+    );
+    assert_eq!(
+        data_factory.from_rdfjs(&RdfJsTerm::literal("foo")),
+        Ok(data_factory.simple_literal("foo").into())
+    );
+    assert_eq!(
+        data_factory.from_rdfjs(
+            &RdfJsTerm::literal("foo").with_datatype(RdfJsTerm::named_node(
+                "http://www.w3.org/2001/XMLSchema#string"
+            ))
+        ),
+        Ok(data_factory.simple_literal("foo").into())
+    );
+    assert_eq!(
+        data_factory.from_rdfjs(&RdfJsTerm::literal("foo").with_language("en")),
+        Ok(data_factory.language_tagged_literal("foo", "en").into())
+    );
+    let integer = "http://www.w3.org/2001/XMLSchema#integer";
+    assert_eq!(
+        data_factory.from_rdfjs(&RdfJsTerm {
+            term_type: "Literal".to_owned(),
+            value: "1".to_owned(),
+            language: String::default(),
+            datatype: Some(Box::new(RdfJsTerm::named_node(integer))),
+        }),
+        Ok(data_factory
+            .typed_literal("1", data_factory.named_node(integer))
+            .into())
+    );
+
+    assert_eq!(
+        data_factory.from_rdfjs(&RdfJsTerm {
+            term_type: "DefaultGraph".to_owned(),
+            ..RdfJsTerm::default()
+        }),
+        Err(RdfJsTermError::UnsupportedTermType(
+            "DefaultGraph".to_owned()
+        ))
+    );
+    assert_eq!(
+        data_factory
+            .from_rdfjs(&RdfJsTerm::literal("foo").with_datatype(RdfJsTerm::blank_node("b"))),
+        Err(RdfJsTermError::InvalidDatatype("BlankNode".to_owned()))
+    );
+    assert_eq!(
+        data_factory.from_rdfjs(
+            &RdfJsTerm::literal("foo").with_datatype(RdfJsTerm::named_node(
+                "http://www.w3.org/1999/02/22-rdf-syntax-ns#langString"
+            ))
+        ),
+        Err(RdfJsTermError::InvalidLiteral(
+            LiteralError::LangStringWithoutLanguage
+        ))
+    );
+}
+
+#[test]
+fn test_term_kind() {
+    let data_factory = DataFactory::default();
+    let named_node = data_factory.named_node("http://example.com/s");
+    let blank_node = data_factory.blank_node("b");
+    assert_eq!(Term::from(named_node.clone()).kind(), TermKind::NamedNode);
+    assert_eq!(Term::from(blank_node.clone()).kind(), TermKind::BlankNode);
+    assert_eq!(
+        Term::from(data_factory.simple_literal("foo")).kind(),
+        TermKind::Literal
+    );
+    assert_eq!(
+        NamedOrBlankNode::from(named_node).kind(),
+        TermKind::NamedNode
+    );
+    assert_eq!(
+        NamedOrBlankNode::from(blank_node).kind(),
+        TermKind::BlankNode
+    );
+    assert_eq!(TermKind::BlankNode.to_string(), "blank node");
+}
+
+#[test]
+fn test_numeric_literals() {
+    let data_factory = DataFactory::default();
+    let literal = |value: &str, datatype: &str| {
+        data_factory.typed_literal(
+            value,
+            data_factory.named_node(format!("http://www.w3.org/2001/XMLSchema#{}", datatype)),
+        )
+    };
+    assert_eq!(
+        literal("1", "integer").numeric_datatype(),
+        Some(NumericType::Integer)
+    );
+    assert_eq!(
+        literal("1", "unsignedByte").numeric_datatype(),
+        Some(NumericType::Integer)
+    );
+    assert_eq!(
+        literal("1.5", "decimal").numeric_datatype(),
+        Some(NumericType::Decimal)
+    );
+    assert_eq!(
+        literal("1.5", "float").numeric_datatype(),
+        Some(NumericType::Float)
+    );
+    assert_eq!(
+        literal("1.5", "double").numeric_datatype(),
+        Some(NumericType::Double)
+    );
+    assert_eq!(literal("1", "string").numeric_datatype(), None);
+    assert_eq!(data_factory.simple_literal("1").numeric_datatype(), None);
+    assert_eq!(
+        data_factory
+            .language_tagged_literal("1", "en")
+            .numeric_datatype(),
+        None
+    );
+
+    assert_eq!(literal("+042", "int").as_f64(), Some(42.));
+    assert_eq!(literal("-1.50", "decimal").as_f64(), Some(-1.5));
+    assert_eq!(literal("1.5E2", "double").as_f64(), Some(150.));
+    assert_eq!(literal("0.1", "float").as_f64(), Some(f64::from(0.1f32)));
+    assert_eq!(literal("0.1", "double").as_f64(), Some(0.1));
+    assert_eq!(literal("-INF", "double").as_f64(), Some(f64::NEG_INFINITY));
+    assert!(literal("NaN", "float").as_f64().unwrap().is_nan());
+    assert_eq!(literal("inf", "double").as_f64(), None);
+    assert_eq!(literal("1.5", "integer").as_f64(), None);
+    assert_eq!(literal("1", "string").as_f64(), None);
+}
+
+#[test]
+fn test_debug() {
+    let data_factory = DataFactory::default();
+    let triple = data_factory.triple(
+        data_factory.named_node("http://example.com/s"),
+        data_factory.named_node("http://example.com/p"),
+        data_factory.language_tagged_literal("foo", "en"),
+    );
+    assert_eq!(
+        format!("{:?}", triple),
+        "<http://example.com/s> <http://example.com/p> \"foo\"@en ."
+    );
+    assert_eq!(
+        format!("{:?}", vec![triple.object().clone()]),
+        "[\"foo\"@en]"
+    );
+    let verbose = format!("{:#?}", triple);
+    assert!(verbose.starts_with("Triple {"));
+    assert!(verbose.contains("subject: <http://example.com/s>,"));
+    assert!(verbose.contains("object: Literal(\n        \"foo\"@en,"));
+
+    // The terms are escaped like in N-Triples
+    let literal = data_factory.simple_literal("a\n\"b\"");
+    assert_eq!(format!("{:?}", literal), r#""a\n\"b\"""#);
+    let escaped = data_factory.triple(
+        data_factory.blank_node("b"),
+        data_factory.named_node("http://example.com/p q"),
+        literal.clone(),
+    );
+    assert_eq!(
+        format!("{:?}", escaped),
+        r#"_:b <http://example.com/p\u0020q> "a\n\"b\"" ."#
+    );
+    let graph_name = Some(data_factory.named_node("http://example.com/g").into());
+    assert_eq!(
+        format!("{:?}", escaped.in_graph(&graph_name)),
+        r#"_:b <http://example.com/p\u0020q> "a\n\"b\"" <http://example.com/g> ."#
+    );
+    assert_eq!(
+        format!("{:?}", data_factory.named_node("http://example.com/s")),
+        "<http://example.com/s>"
+    );
+    let subject: NamedOrBlankNode = data_factory.blank_node("b").into();
+    assert_eq!(format!("{:?}", subject), "_:b");
+    assert_eq!(format!("{:?}", Term::from(literal)), r#""a\n\"b\"""#);
+}
+
+#[test]
+fn test_date_times() {
+    let data_factory = DataFactory::default();
+    let date_time = |value: &str| {
+        CanonicalLiteral::new(data_factory.typed_literal(
+            value,
+            data_factory.named_node("http://www.w3.org/2001/XMLSchema#dateTime"),
+        ))
+    };
+
+    let utc = date_time("2020-01-01T12:00:00Z");
+    let paris = date_time("2020-01-01T14:00:00+02:00");
+    assert!(utc.value_eq(&paris));
+    // The canonical form keeps the timezone offset
+    assert_eq!(paris.canonical().value(), "2020-01-01T14:00:00+02:00");
+    assert_eq!(utc.value_cmp(&paris), Some(Ordering::Equal));
+    assert_eq!(
+        date_time("2019-12-31T23:30:00-01:00").canonical().value(),
+        "2019-12-31T23:30:00-01:00"
+    );
+    assert_eq!(
+        date_time("2020-01-01T12:00:00+00:00").canonical().value(),
+        "2020-01-01T12:00:00Z"
+    );
+    assert_eq!(
+        date_time("2020-01-01T12:00:00.500+00:00")
+            .canonical()
+            .value(),
+        "2020-01-01T12:00:00.5Z"
+    );
+    assert_eq!(
+        date_time("2020-01-01T12:00:00.500+14:00")
+            .canonical()
+            .value(),
+        "2020-01-01T12:00:00.5+14:00"
+    );
+    assert_eq!(
+        date_time("2019-12-31T24:00:00-05:30").canonical().value(),
+        "2020-01-01T00:00:00-05:30"
+    );
+    assert_eq!(
+        date_time("2019-12-31T24:00:00").canonical().value(),
+        "2020-01-01T00:00:00"
+    );
+    assert_eq!(
+        date_time("-0001-03-01T00:00:00Z").canonical().value(),
+        "-0001-03-01T00:00:00Z"
+    );
+    assert_eq!(
+        utc.value_cmp(&date_time("2020-01-01T13:00:00+02:00")),
+        Some(Ordering::Greater)
+    );
+    assert_eq!(
+        utc.value_cmp(&date_time("2020-01-01T12:00:01Z")),
+        Some(Ordering::Less)
+    );
+
+    // Values with and without timezone are not comparable
+    let local = date_time("2020-01-01T12:00:00");
+    assert!(!utc.value_eq(&local));
+    assert_eq!(utc.value_cmp(&local), None);
+    assert_eq!(local.value_cmp(&local), Some(Ordering::Equal));
+
+    // Invalid lexical forms are kept
+    for value in &[
+        "2020-02-30T12:00:00Z",
+        "2020-01-01T12:00:00+15:00",
+        "2020-01-01T24:00:01",
+        "2020-01-01 12:00:00",
+        "20-01-01T12:00:00",
+    ] {
+        assert_eq!(date_time(value).canonical().value(), *value);
+        assert_eq!(date_time(value).value_cmp(&utc), None);
+    }
+    assert_eq!(
+        XsdDateTime::parse("2020-02-29T12:00:00-05:30")
+            .unwrap()
+            .timezone_offset(),
+        Some(-330)
+    );
+
+    // The years out of the i64 days range do not overflow
+    let far = "999999999999999999-01-01T00:00:00Z";
+    assert_eq!(XsdDateTime::parse(far).unwrap().to_string(), far);
+    assert!(XsdDateTime::parse(far) > XsdDateTime::parse("2020-01-01T00:00:00Z"));
+    assert_eq!(
+        XsdDateTime::parse("99999999999999999999-01-01T00:00:00Z"),
+        None
+    );
+}
+
+#[test]
+fn test_triple_checked() {
+    let data_factory = DataFactory::default();
+    let iri: Term = data_factory.named_node("http://example.com/s").into();
+    let blank_node: Term = data_factory.blank_node("b").into();
+    let literal: Term = data_factory.simple_literal("foo").into();
+
+    assert_eq!(
+        data_factory.triple_checked(blank_node.clone(), iri.clone(), literal.clone()),
+        Ok(data_factory.triple(
+            data_factory.blank_node("b"),
+            data_factory.named_node("http://example.com/s"),
+            data_factory.simple_literal("foo"),
+        ))
+    );
+
+    let error = data_factory
+        .triple_checked(literal.clone(), iri.clone(), iri.clone())
+        .unwrap_err();
+    assert_eq!(error, TripleError::InvalidSubject(literal.clone()));
+    assert_eq!(
+        error.to_string(),
+        "The literal \"foo\" can not be the subject of a triple, only an IRI or a blank node can"
+    );
+    assert_eq!(
+        data_factory.triple_checked(iri.clone(), blank_node.clone(), iri.clone()),
+        Err(TripleError::InvalidPredicate(blank_node))
+    );
+    assert_eq!(
+        data_factory.triple_checked(iri.clone(), literal.clone(), iri),
+        Err(TripleError::InvalidPredicate(literal))
+    );
+}
@@ -0,0 +1,80 @@
+extern crate rudf;
+
+use rudf::model::data::*;
+use rudf::model::graph::*;
+use rudf::model::pattern::*;
+use rudf::rio::turtle::read_turtle;
+
+#[test]
+fn test_construct() {
+    let data_factory = DataFactory::default();
+    let graph: Graph = read_turtle(
+        "@prefix ex: <http://example.com/> .
+        ex:a ex:oldName \"A\" .
+        ex:b ex:oldName \"B\" ; ex:other \"C\" ."
+            .as_bytes(),
+        &data_factory,
+    )
+    .unwrap()
+    .collect();
+    let ex = |name: &str| data_factory.named_node(format!("http://example.com/{}", name));
+
+    // The bindings of the pattern ?s ex:oldName ?o
+    let bindings: Vec<Bindings> = graph
+        .iter()
+        .filter(|triple| *triple.predicate() == ex("oldName"))
+        .map(|triple| {
+            let mut solution = Bindings::new();
+            solution.insert("s".to_owned(), triple.subject().clone().into());
+            solution.insert("o".to_owned(), triple.object().clone());
+            solution
+        })
+        .collect();
+
+    let template = [TriplePattern::new(
+        TermPattern::variable("s"),
+        ex("newName"),
+        TermPattern::variable("o"),
+    )];
+    let expected: Graph = vec![
+        data_factory.triple(ex("a"), ex("newName"), data_factory.simple_literal("A")),
+        data_factory.triple(ex("b"), ex("newName"), data_factory.simple_literal("B")),
+    ]
+    .into_iter()
+    .collect();
+    assert!(construct(&template, &bindings, &data_factory).ground_eq(&expected));
+
+    // Fresh blank nodes are built for each solution
+    let template = [
+        TriplePattern::new(
+            TermPattern::variable("s"),
+            ex("name"),
+            data_factory.blank_node("n"),
+        ),
+        TriplePattern::new(
+            data_factory.blank_node("n"),
+            ex("value"),
+            TermPattern::variable("o"),
+        ),
+        // Skipped because of the unbound variable and the literal subject
+        TriplePattern::new(
+            TermPattern::variable("s"),
+            ex("p"),
+            TermPattern::variable("unbound"),
+        ),
+        TriplePattern::new(TermPattern::variable("o"), ex("p"), ex("o")),
+    ];
+    let result = construct(&template, &bindings, &data_factory);
+    assert_eq!(result.len(), 4);
+    assert_eq!(result.blank_nodes().count(), 2);
+    for triple in result
+        .iter()
+        .filter(|triple| *triple.predicate() == ex("name"))
+    {
+        let node = match triple.object() {
+            Term::BlankNode(node) => NamedOrBlankNode::from(node.clone()),
+            _ => panic!("The object should be a blank node"),
+        };
+        assert_eq!(result.out_degree(&node), 1);
+    }
+}
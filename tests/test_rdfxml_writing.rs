@@ -0,0 +1,184 @@
+extern crate rudf;
+
+use rudf::model::data::*;
+use rudf::rio::rdfxml;
+use std::collections::HashMap;
+
+#[test]
+fn test_write_rdfxml() {
+    let data_factory = DataFactory::default();
+    let s = data_factory.named_node("http://example.com/s");
+    let b = data_factory.blank_node("b1");
+    let triples = vec![
+        data_factory.triple(
+            s.clone(),
+            data_factory.named_node("http://example.com/knows"),
+            b.clone(),
+        ),
+        data_factory.triple(
+            b.clone(),
+            data_factory.named_node("http://example.com/name"),
+            data_factory.language_tagged_literal("Tom & Jerry", "en"),
+        ),
+        data_factory.triple(
+            s.clone(),
+            data_factory.named_node("http://example.com/seeAlso"),
+            data_factory.named_node("http://example.com/o"),
+        ),
+        data_factory.triple(
+            s,
+            data_factory.named_node("http://xmlns.com/foaf/0.1/age"),
+            data_factory.typed_literal(
+                "42",
+                data_factory.named_node("http://www.w3.org/2001/XMLSchema#integer"),
+            ),
+        ),
+        data_factory.triple(
+            b,
+            data_factory.named_node("http://example.com/comment"),
+            data_factory.simple_literal("a < b"),
+        ),
+    ];
+    let mut prefixes = HashMap::default();
+    prefixes.insert("ex".to_owned(), "http://example.com/".to_owned());
+
+    let mut output = Vec::default();
+    rdfxml::write_rdfxml(&triples, &prefixes, &mut output).unwrap();
+    assert_eq!(
+        String::from_utf8(output).unwrap(),
+        r#"<?xml version="1.0" encoding="UTF-8"?>
+<rdf:RDF xmlns:ex="http://example.com/" xmlns:ns1="http://xmlns.com/foaf/0.1/" xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#">
+  <rdf:Description rdf:about="http://example.com/s">
+    <ex:knows rdf:nodeID="b1"/>
+    <ex:seeAlso rdf:resource="http://example.com/o"/>
+    <ns1:age rdf:datatype="http://www.w3.org/2001/XMLSchema#integer">42</ns1:age>
+  </rdf:Description>
+  <rdf:Description rdf:nodeID="b1">
+    <ex:name xml:lang="en">Tom &amp; Jerry</ex:name>
+    <ex:comment>a &lt; b</ex:comment>
+  </rdf:Description>
+</rdf:RDF>
+"#
+    );
+}
+
+#[test]
+fn test_write_rdfxml_invalid_predicate() {
+    let data_factory = DataFactory::default();
+    let triples = vec![data_factory.triple(
+        data_factory.named_node("http://example.com/s"),
+        data_factory.named_node("http://example.com/1"),
+        data_factory.named_node("http://example.com/o"),
+    )];
+    assert!(rdfxml::write_rdfxml(&triples, &HashMap::default(), Vec::default()).is_err());
+}
+
+#[test]
+fn test_write_rdfxml_prefixes() {
+    let data_factory = DataFactory::default();
+    let triples = vec![
+        data_factory.triple(
+            data_factory.named_node("http://example.com/s"),
+            data_factory.named_node("http://example.com/p"),
+            data_factory.named_node("http://example.com/o"),
+        ),
+        data_factory.triple(
+            data_factory.named_node("http://example.com/s"),
+            data_factory.named_node("http://example.org/p"),
+            data_factory.named_node("http://example.com/o"),
+        ),
+    ];
+    let mut prefixes = HashMap::default();
+    prefixes.insert("ns1".to_owned(), "http://example.net/".to_owned());
+    prefixes.insert("".to_owned(), "http://example.com/".to_owned());
+
+    let mut output = Vec::default();
+    rdfxml::write_rdfxml(&triples, &prefixes, &mut output).unwrap();
+    let output = String::from_utf8(output).unwrap();
+    // The generated prefixes do not override the given ones and the empty prefix is not used
+    assert!(output.contains(
+        r#"<rdf:RDF xmlns:ns1="http://example.net/" xmlns:ns2="http://example.com/" xmlns:ns3="http://example.org/" xmlns:rdf="#
+    ));
+    assert!(!output.contains("xmlns:="));
+    assert!(output.contains(r#"<ns2:p rdf:resource="http://example.com/o"/>"#));
+    assert!(output.contains(r#"<ns3:p rdf:resource="http://example.com/o"/>"#));
+}
+
+#[test]
+fn test_write_rdfxml_control_characters() {
+    let data_factory = DataFactory::default();
+    let write = |value: &str| {
+        let triples = vec![data_factory.triple(
+            data_factory.named_node("http://example.com/s"),
+            data_factory.named_node("http://example.com/p"),
+            data_factory.simple_literal(value),
+        )];
+        let mut output = Vec::default();
+        rdfxml::write_rdfxml(&triples, &HashMap::default(), &mut output)
+            .map(|_| String::from_utf8(output).unwrap())
+    };
+    assert!(write("a\r\n\tb")
+        .unwrap()
+        .contains("<ns1:p>a&#13;&#10;&#9;b</ns1:p>"));
+    assert!(write("a\u{1}b").is_err());
+}
+
+#[test]
+fn test_write_rdfxml_node_ids() {
+    // There is no RDF/XML reader in the crate to parse the output back, so the node ids are checked in the written document
+    let data_factory = DataFactory::default();
+    let node = data_factory.new_blank_node();
+    let id = node.value().to_owned();
+    assert!(id.starts_with(|c: char| c.is_ascii_digit()));
+    let triples = vec![
+        data_factory.triple(
+            data_factory.named_node("http://e/s"),
+            data_factory.named_node("http://e/p"),
+            node.clone(),
+        ),
+        data_factory.triple(
+            node,
+            data_factory.named_node("http://e/q"),
+            data_factory.simple_literal("x"),
+        ),
+    ];
+
+    let write = |triples: &[Triple]| {
+        let mut output = Vec::default();
+        rdfxml::write_rdfxml(triples, &HashMap::default(), &mut output).unwrap();
+        String::from_utf8(output).unwrap()
+    };
+    let output = write(&triples);
+    assert!(!output.contains(&format!("rdf:nodeID=\"{}\"", id)));
+    assert_eq!(
+        output.matches(&format!("rdf:nodeID=\"b{}\"", id)).count(),
+        2
+    );
+
+    // The replacement ids do not conflict with the ids already used
+    let mut triples = triples;
+    triples.push(data_factory.triple(
+        data_factory.blank_node(format!("b{}", id)),
+        data_factory.named_node("http://e/q"),
+        data_factory.simple_literal("y"),
+    ));
+    let uuid_node = DataFactory::with_uuid_blank_nodes().new_blank_node();
+    triples.push(data_factory.triple(
+        uuid_node,
+        data_factory.named_node("http://e/q"),
+        data_factory.simple_literal("z"),
+    ));
+    let output = write(&triples);
+    let mut node_ids: Vec<&str> = output
+        .split("rdf:nodeID=\"")
+        .skip(1)
+        .map(|rest| &rest[..rest.find('"').unwrap()])
+        .collect();
+    assert_eq!(node_ids.len(), 4);
+    assert!(node_ids
+        .iter()
+        .all(|id| id.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_')));
+    node_ids.sort();
+    node_ids.dedup();
+    assert_eq!(node_ids.len(), 3);
+}
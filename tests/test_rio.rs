@@ -0,0 +1,803 @@
+extern crate rudf;
+
+use rudf::model::data::*;
+use rudf::model::graph::*;
+use rudf::rio::ntriples::*;
+use rudf::rio::turtle;
+use rudf::rio::*;
+use std::collections::HashMap;
+use std::collections::HashSet;
+use std::env;
+use std::error::Error;
+use std::fs;
+use std::io;
+use std::io::Read;
+use std::path::Path;
+use std::process;
+
+#[test]
+fn test_dedup() {
+    let data_factory = DataFactory::default();
+    let s = data_factory.named_node("http://example.com/s");
+    let p = data_factory.named_node("http://example.com/p");
+    let t1 = data_factory.triple(s.clone(), p.clone(), data_factory.simple_literal("1"));
+    let t2 = data_factory.triple(s, p, data_factory.simple_literal("2"));
+    let triples = vec![t1.clone(), t2.clone(), t1.clone(), t2.clone(), t1.clone()];
+    assert_eq!(dedup(triples.into_iter()).collect::<Vec<_>>(), vec![t1, t2]);
+}
+
+struct CountingSink {
+    count: usize,
+    finished: bool,
+}
+
+impl TripleSink for CountingSink {
+    fn push(&mut self, _: &Triple) -> RioResult<()> {
+        self.count += 1;
+        Ok(())
+    }
+
+    fn finish(&mut self) -> RioResult<()> {
+        self.finished = true;
+        Ok(())
+    }
+}
+
+fn example_triples(data_factory: &DataFactory) -> Vec<Triple> {
+    let s = data_factory.named_node("http://example.com/s");
+    let p = data_factory.named_node("http://example.com/p");
+    vec![
+        data_factory.triple(s.clone(), p.clone(), data_factory.blank_node("b")),
+        data_factory.triple(
+            s.clone(),
+            p.clone(),
+            data_factory.simple_literal("a \"quoted\"\nstring\\"),
+        ),
+        data_factory.triple(
+            data_factory.blank_node("b"),
+            p.clone(),
+            data_factory.language_tagged_literal("chat", "fr"),
+        ),
+        data_factory.triple(
+            s,
+            p,
+            data_factory.typed_literal(
+                "1",
+                data_factory.named_node("http://www.w3.org/2001/XMLSchema#integer"),
+            ),
+        ),
+    ]
+}
+
+fn copy_to_sink(triples: &[Triple], sink: &mut dyn TripleSink) -> RioResult<()> {
+    for triple in triples {
+        sink.push(triple)?;
+    }
+    sink.finish()
+}
+
+#[test]
+fn test_custom_sink() {
+    let data_factory = DataFactory::default();
+    let mut sink = CountingSink {
+        count: 0,
+        finished: false,
+    };
+    copy_to_sink(&example_triples(&data_factory), &mut sink).unwrap();
+    assert_eq!(sink.count, 4);
+    assert!(sink.finished);
+}
+
+#[test]
+fn test_ntriples_sink() {
+    let data_factory = DataFactory::default();
+    let triples = example_triples(&data_factory);
+    let mut sink = ntriples::NTriplesSink::new(Vec::default());
+    copy_to_sink(&triples, &mut sink).unwrap();
+    let output = sink.into_inner();
+    assert_eq!(
+        String::from_utf8(output.clone()).unwrap(),
+        "<http://example.com/s> <http://example.com/p> _:b .\n\
+         <http://example.com/s> <http://example.com/p> \"a \\\"quoted\\\"\\nstring\\\\\" .\n\
+         _:b <http://example.com/p> \"chat\"@fr .\n\
+         <http://example.com/s> <http://example.com/p> \"1\"^^<http://www.w3.org/2001/XMLSchema#integer> .\n"
+    );
+    let parsed: Vec<Triple> = ntriples::read_ntriples(output.as_slice(), &data_factory)
+        .collect::<RioResult<_>>()
+        .unwrap();
+    assert_eq!(parsed, triples);
+}
+
+#[test]
+fn test_ntriples_escape_style() {
+    let data_factory = DataFactory::default();
+    let triples = vec![data_factory.triple(
+        data_factory.named_node("http://example.com/é"),
+        data_factory.named_node("http://example.com/p"),
+        data_factory.simple_literal("café 😀"),
+    )];
+
+    let mut utf8 = NTriplesSink::new(Vec::default());
+    copy_to_sink(&triples, &mut utf8).unwrap();
+    assert_eq!(
+        String::from_utf8(utf8.into_inner()).unwrap(),
+        "<http://example.com/é> <http://example.com/p> \"café 😀\" .\n"
+    );
+
+    let mut ascii = NTriplesSink::with_escape_style(Vec::default(), EscapeStyle::AsciiOnly);
+    copy_to_sink(&triples, &mut ascii).unwrap();
+    let output = ascii.into_inner();
+    assert_eq!(
+        String::from_utf8(output.clone()).unwrap(),
+        "<http://example.com/\\u00E9> <http://example.com/p> \"caf\\u00E9 \\U0001F600\" .\n"
+    );
+    let parsed: Vec<Triple> = ntriples::read_ntriples(output.as_slice(), &data_factory)
+        .collect::<RioResult<_>>()
+        .unwrap();
+    assert_eq!(parsed, triples);
+}
+
+#[test]
+fn test_read_ntriples_parallel() {
+    let data_factory = DataFactory::default();
+    let mut file = String::default();
+    for i in 0..1000 {
+        file.push_str(&format!(
+            "<http://example.com/s{}> <http://example.com/p> \"o{}\" .\n_:b <http://example.com/p> <http://example.com/s{}> .\n\n# comment\n",
+            i, i, i
+        ));
+    }
+    let sequential: Vec<Triple> = read_ntriples(file.as_bytes(), &data_factory)
+        .collect::<RioResult<_>>()
+        .unwrap();
+    let parallel = read_ntriples_parallel(file.as_bytes(), &data_factory).unwrap();
+    assert_eq!(parallel.len(), 2000);
+    assert_eq!(parallel, sequential);
+
+    assert!(read_ntriples_parallel("<http://example.com/s> .".as_bytes(), &data_factory).is_err());
+}
+
+#[test]
+fn test_empty_inputs() {
+    let data_factory = DataFactory::default();
+    for input in &[
+        "",
+        " \n\t\r\n",
+        "# comment\n",
+        "\n# comment\n  # other comment",
+    ] {
+        assert_eq!(
+            read_ntriples(input.as_bytes(), &data_factory)
+                .collect::<RioResult<Vec<_>>>()
+                .unwrap(),
+            Vec::default(),
+            "N-Triples input {:?}",
+            input
+        );
+        assert!(
+            read_ntriples_parallel(input.as_bytes(), &data_factory)
+                .unwrap()
+                .is_empty(),
+            "N-Triples input {:?}",
+            input
+        );
+        assert_eq!(
+            turtle::read_turtle(input.as_bytes(), &data_factory)
+                .unwrap()
+                .count(),
+            0,
+            "Turtle input {:?}",
+            input
+        );
+        let mut parser = turtle::TurtleParser::new(&data_factory);
+        assert!(parser.push_chunk(input.as_bytes()).is_empty());
+        assert!(parser.finish().unwrap().is_empty());
+        let (base, prefixes) = turtle::read_prefixes(input.as_bytes()).unwrap();
+        assert_eq!(base, None);
+        assert!(prefixes.is_empty());
+    }
+}
+
+#[test]
+fn test_to_string() {
+    let data_factory = DataFactory::default();
+    let s = data_factory.named_node("http://example.com/s");
+    let p = data_factory.named_node("http://example.com/p");
+    let g = data_factory.named_node("http://example.com/g");
+    let triples = vec![
+        data_factory.triple(
+            s.clone(),
+            p.clone(),
+            data_factory.simple_literal("foo\n\"bar\""),
+        ),
+        data_factory.triple(
+            s.clone(),
+            p.clone(),
+            data_factory.language_tagged_literal("foo", "en"),
+        ),
+        data_factory.triple(data_factory.blank_node("b"), p.clone(), s.clone()),
+    ];
+
+    let ntriples = ntriples_to_string(&triples);
+    let reparsed: Vec<Triple> = read_ntriples(ntriples.as_bytes(), &data_factory)
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(reparsed, triples);
+
+    let mut prefixes = HashMap::default();
+    prefixes.insert("ex".to_owned(), "http://example.com/".to_owned());
+    let turtle = turtle::turtle_to_string(&triples, &prefixes);
+    assert!(turtle.starts_with("@prefix ex: <http://example.com/> .\n"));
+    let reparsed: Vec<Triple> = turtle::read_turtle(turtle.as_bytes(), &data_factory)
+        .unwrap()
+        .collect();
+    assert_eq!(reparsed, triples);
+
+    let quads = vec![
+        triples[0].clone().in_graph(&None),
+        triples[2].clone().in_graph(&Some(g.into())),
+    ];
+    assert_eq!(
+        nquads_to_string(&quads),
+        "<http://example.com/s> <http://example.com/p> \"foo\\n\\\"bar\\\"\" .\n_:b <http://example.com/p> <http://example.com/s> <http://example.com/g> .\n"
+    );
+}
+
+struct FailingRead;
+
+impl Read for FailingRead {
+    fn read(&mut self, _: &mut [u8]) -> io::Result<usize> {
+        Err(io::Error::new(io::ErrorKind::PermissionDenied, "denied"))
+    }
+}
+
+fn read_first_triple() -> Result<Option<Triple>, Box<dyn Error>> {
+    let data_factory = DataFactory::default();
+    Ok(read_ntriples(FailingRead, &data_factory)
+        .next()
+        .transpose()?)
+}
+
+#[test]
+fn test_error_sources() {
+    let error = read_first_triple().unwrap_err();
+    let rio_error = error.downcast_ref::<RioError>().unwrap();
+    assert_eq!(
+        rio_error.downcast_ref::<io::Error>().unwrap().kind(),
+        io::ErrorKind::PermissionDenied
+    );
+    let source = rio_error.source().unwrap();
+    assert_eq!(
+        source.downcast_ref::<io::Error>().unwrap().kind(),
+        io::ErrorKind::PermissionDenied
+    );
+    assert_eq!(rio_error.to_string(), "denied");
+
+    let data_factory = DataFactory::default();
+    let error = turtle::read_turtle_path(Path::new("does/not/exist.ttl"), &data_factory)
+        .err()
+        .unwrap();
+    assert_eq!(
+        error
+            .source()
+            .unwrap()
+            .downcast_ref::<io::Error>()
+            .unwrap()
+            .kind(),
+        io::ErrorKind::NotFound
+    );
+}
+
+#[test]
+fn test_write_turtle_pretty() {
+    let data_factory = DataFactory::default();
+    let s = data_factory.named_node("http://example.com/s");
+    let p = data_factory.named_node("http://example.com/p");
+    let q = data_factory.named_node("http://example.com/q");
+    let triples = vec![
+        data_factory.triple(s.clone(), p.clone(), data_factory.simple_literal("a")),
+        data_factory.triple(
+            s.clone(),
+            q.clone(),
+            data_factory.named_node("http://example.com/o"),
+        ),
+        data_factory.triple(data_factory.blank_node("b"), p.clone(), s.clone()),
+        data_factory.triple(s.clone(), p.clone(), data_factory.simple_literal("b")),
+    ];
+    let mut prefixes = HashMap::default();
+    prefixes.insert("ex".to_owned(), "http://example.com/".to_owned());
+    let write = |indent| {
+        let mut buffer = Vec::default();
+        turtle::write_turtle_pretty(
+            &triples,
+            &prefixes,
+            &turtle::TurtleWriterConfig { indent },
+            &mut buffer,
+        )
+        .unwrap();
+        String::from_utf8(buffer).unwrap()
+    };
+
+    let two_spaces = write(turtle::Indent::Spaces(2));
+    assert_eq!(
+        two_spaces,
+        "@prefix ex: <http://example.com/> .\n\nex:s ex:p \"a\", \"b\" ;\n  ex:q ex:o .\n\n_:b ex:p ex:s .\n"
+    );
+    assert_eq!(
+        write(turtle::Indent::Spaces(4)),
+        two_spaces.replace("\n  ex:q", "\n    ex:q")
+    );
+    assert_eq!(
+        write(turtle::Indent::Tabs(1)),
+        two_spaces.replace("\n  ex:q", "\n\tex:q")
+    );
+    assert_eq!(
+        turtle::TurtleWriterConfig::default().indent,
+        turtle::Indent::Spaces(4)
+    );
+
+    let mut reparsed: Vec<Triple> = turtle::read_turtle(two_spaces.as_bytes(), &data_factory)
+        .unwrap()
+        .collect();
+    reparsed.sort();
+    let mut expected = triples.clone();
+    expected.sort();
+    assert_eq!(reparsed, expected);
+
+    let mut buffer = Vec::default();
+    turtle::write_turtle_pretty(
+        &triples[2..3],
+        &HashMap::default(),
+        &turtle::TurtleWriterConfig::default(),
+        &mut buffer,
+    )
+    .unwrap();
+    assert_eq!(
+        String::from_utf8(buffer).unwrap(),
+        "_:b <http://example.com/p> <http://example.com/s> .\n"
+    );
+}
+
+#[test]
+fn test_buffer_sizes() {
+    let data_factory = DataFactory::default();
+    let ntriples = (0..100)
+        .map(|i| {
+            format!(
+                "<http://example.com/s> <http://example.com/p> \"{}é\" .\n",
+                i
+            )
+        })
+        .collect::<String>();
+    let expected: Vec<Triple> = read_ntriples(ntriples.as_bytes(), &data_factory)
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(expected.len(), 100);
+    for buffer_size in &[0, 1, 7, 4096, DEFAULT_BUFFER_SIZE] {
+        let options = NTriplesOptions::default().with_buffer_size(*buffer_size);
+        let triples: Vec<Triple> =
+            read_ntriples_with_options(ntriples.as_bytes(), &data_factory, &options)
+                .collect::<Result<_, _>>()
+                .unwrap();
+        assert_eq!(triples, expected);
+
+        let options = turtle::TurtleOptions::default().with_buffer_size(*buffer_size);
+        let triples: Vec<Triple> =
+            turtle::read_turtle_with_options(ntriples.as_bytes(), &data_factory, &options)
+                .unwrap()
+                .collect();
+        assert_eq!(triples, expected);
+
+        let options = turtle::TurtleOptions::default()
+            .with_buffer_size(*buffer_size)
+            .with_limit(50);
+        let triples: Vec<Triple> =
+            turtle::read_turtle_with_options(ntriples.as_bytes(), &data_factory, &options)
+                .unwrap()
+                .collect();
+        assert_eq!(triples, &expected[..50]);
+    }
+    assert_eq!(NTriplesOptions::default().buffer_size, 64 * 1024);
+    assert_eq!(turtle::TurtleOptions::default().buffer_size, 64 * 1024);
+}
+
+#[test]
+fn test_suggest_prefixes() {
+    let iris = [
+        "http://example.com/vocab#a",
+        "http://example.com/vocab#b",
+        "http://example.com/vocab#c",
+        "http://www.w3.org/1999/02/22-rdf-syntax-ns#type",
+        "http://www.w3.org/1999/02/22-rdf-syntax-ns#value",
+        "http://example.com/data/1",
+        "http://example.com/data/not%20local",
+    ];
+
+    let prefixes = turtle::suggest_prefixes(iris.iter().cloned(), 1);
+    assert_eq!(prefixes.len(), 1);
+    assert_eq!(prefixes["vocab"], "http://example.com/vocab#");
+
+    let prefixes = turtle::suggest_prefixes(iris.iter().cloned(), 10);
+    let mut expected = HashMap::new();
+    expected.insert("vocab".to_owned(), "http://example.com/vocab#".to_owned());
+    expected.insert(
+        "rdf".to_owned(),
+        "http://www.w3.org/1999/02/22-rdf-syntax-ns#".to_owned(),
+    );
+    expected.insert("data".to_owned(), "http://example.com/data/".to_owned());
+    assert_eq!(prefixes, expected);
+
+    // The prefix names are unique
+    let prefixes = turtle::suggest_prefixes(
+        vec!["http://a.example/ns/x", "http://b.example/ns/x", "urn:x"],
+        10,
+    );
+    let mut names: Vec<_> = prefixes.keys().cloned().collect();
+    names.sort();
+    assert_eq!(names, vec!["ns", "ns1"]);
+}
+
+#[test]
+fn test_join_by_subject() {
+    let data_factory = DataFactory::default();
+    let triple = |subject: &str, object: &str| {
+        data_factory.triple(
+            data_factory.named_node(format!("http://example.com/{}", subject)),
+            data_factory.named_node("http://example.com/p"),
+            data_factory.simple_literal(object),
+        )
+    };
+    let left = vec![triple("a", "1"), triple("a", "2"), triple("b", "3")];
+    let right = vec![triple("a", "4"), triple("c", "5"), triple("c", "6")];
+
+    let groups: Vec<_> = join_by_subject(left, right)
+        .map(|(subject, left, right)| (subject.value().to_owned(), left.len(), right.len()))
+        .collect();
+    assert_eq!(
+        groups,
+        vec![
+            ("http://example.com/a".to_owned(), 2, 1),
+            ("http://example.com/b".to_owned(), 1, 0),
+            ("http://example.com/c".to_owned(), 0, 2),
+        ]
+    );
+
+    let (subject, left, right) = join_by_subject(vec![triple("a", "1")], vec![triple("a", "2")])
+        .next()
+        .unwrap();
+    assert_eq!(subject, triple("a", "1").subject().clone());
+    assert_eq!(left, vec![triple("a", "1")]);
+    assert_eq!(right, vec![triple("a", "2")]);
+    assert_eq!(join_by_subject(Vec::new(), Vec::new()).count(), 0);
+}
+
+#[test]
+fn test_empty_and_whitespace_literals() {
+    let data_factory = DataFactory::default();
+    let s = data_factory.named_node("http://example.com/s");
+    let p = data_factory.named_node("http://example.com/p");
+    let triples: Vec<Triple> = ["", " ", "\t\n", "\r\n  \t"]
+        .iter()
+        .map(|value| data_factory.triple(s.clone(), p.clone(), data_factory.simple_literal(*value)))
+        .chain(Some(data_factory.triple(
+            s.clone(),
+            p.clone(),
+            data_factory.language_tagged_literal("", "en"),
+        )))
+        .collect();
+
+    let ntriples = ntriples_to_string(&triples);
+    assert!(ntriples.starts_with("<http://example.com/s> <http://example.com/p> \"\" .\n"));
+    assert!(ntriples.contains(" \"\\t\\n\" .\n"));
+    let reparsed: Vec<Triple> = read_ntriples(ntriples.as_bytes(), &data_factory)
+        .collect::<Result<_, _>>()
+        .unwrap();
+    assert_eq!(reparsed, triples);
+
+    let turtle = turtle::turtle_to_string(&triples, &HashMap::default());
+    let reparsed: Vec<Triple> = turtle::read_turtle(turtle.as_bytes(), &data_factory)
+        .unwrap()
+        .collect();
+    assert_eq!(reparsed, triples);
+
+    let mut pretty = Vec::default();
+    turtle::write_turtle_pretty(
+        &triples,
+        &HashMap::default(),
+        &turtle::TurtleWriterConfig::default(),
+        &mut pretty,
+    )
+    .unwrap();
+    let mut reparsed: Vec<Triple> = turtle::read_turtle(pretty.as_slice(), &data_factory)
+        .unwrap()
+        .collect();
+    reparsed.sort();
+    let mut expected = triples.clone();
+    expected.sort();
+    assert_eq!(reparsed, expected);
+
+    // The empty literals are also parsed from the other string syntaxes
+    let reparsed: Vec<Triple> = turtle::read_turtle(
+        "<http://example.com/s> <http://example.com/p> '', \"\"\"\"\"\", '''''' .".as_bytes(),
+        &data_factory,
+    )
+    .unwrap()
+    .collect();
+    assert_eq!(reparsed, vec![triples[0].clone(); 3]);
+}
+
+#[test]
+fn test_counting() {
+    let data_factory = DataFactory::default();
+    let file = (0..10)
+        .map(|i| {
+            format!(
+                "<http://example.com/s> <http://example.com/p> \"{}\" .\n",
+                i
+            )
+        })
+        .collect::<String>();
+
+    let mut triples = Counting::new(read_ntriples(file.as_bytes(), &data_factory));
+    assert_eq!(triples.count_so_far(), 0);
+    triples.next().unwrap().unwrap();
+    triples.next().unwrap().unwrap();
+    assert_eq!(triples.count_so_far(), 2);
+    for triple in &mut triples {
+        triple.unwrap();
+    }
+    assert_eq!(triples.count_so_far(), 10);
+    assert!(triples.next().is_none());
+    assert_eq!(triples.count_so_far(), 10);
+}
+
+#[test]
+fn test_streaming_turtle_writer() {
+    let data_factory = DataFactory::default();
+    let mut prefixes = HashMap::default();
+    prefixes.insert("ex".to_owned(), "http://example.com/".to_owned());
+    prefixes.insert(
+        "xsd".to_owned(),
+        "http://www.w3.org/2001/XMLSchema#".to_owned(),
+    );
+    // The prefixes are declared before any triple
+    let header = turtle::StreamingTurtleWriter::new(Vec::default(), prefixes.clone())
+        .unwrap()
+        .into_inner();
+    assert_eq!(
+        String::from_utf8(header).unwrap(),
+        "@prefix ex: <http://example.com/> .\n@prefix xsd: <http://www.w3.org/2001/XMLSchema#> .\n\n"
+    );
+    let mut writer = turtle::StreamingTurtleWriter::new(Vec::default(), prefixes).unwrap();
+
+    let triples = vec![
+        data_factory.triple(
+            data_factory.named_node("http://example.com/s"),
+            data_factory.named_node("http://example.com/p"),
+            data_factory.named_node("http://other.example/o"),
+        ),
+        data_factory.triple(
+            data_factory.named_node("http://other.example/s"),
+            data_factory.named_node("http://example.com/p"),
+            data_factory.typed_literal(
+                "1",
+                data_factory.named_node("http://www.w3.org/2001/XMLSchema#int"),
+            ),
+        ),
+    ];
+    copy_to_sink(&triples, &mut writer).unwrap();
+    let output = writer.into_inner();
+    assert_eq!(
+        String::from_utf8(output.clone()).unwrap(),
+        "@prefix ex: <http://example.com/> .\n\
+         @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .\n\
+         \n\
+         ex:s ex:p <http://other.example/o> .\n\
+         <http://other.example/s> ex:p \"1\"^^xsd:int .\n"
+    );
+    let parsed: Vec<Triple> = turtle::read_turtle(output.as_slice(), &data_factory)
+        .unwrap()
+        .collect();
+    assert_eq!(parsed, triples);
+}
+
+#[test]
+fn test_quad_to_nquads_line() {
+    let data_factory = DataFactory::default();
+    let s = data_factory.named_node("http://example.com/s");
+    let p = data_factory.named_node("http://example.com/p");
+    let o = data_factory.simple_literal("foo\n\"bar\"");
+    assert_eq!(
+        quad_to_nquads_line(&data_factory.quad(s.clone(), p.clone(), o.clone(), None)),
+        "<http://example.com/s> <http://example.com/p> \"foo\\n\\\"bar\\\"\" ."
+    );
+    assert_eq!(
+        quad_to_nquads_line(&data_factory.quad(
+            s.clone(),
+            p.clone(),
+            data_factory.language_tagged_literal("foo", "en"),
+            Some(data_factory.named_node("http://example.com/g a").into())
+        )),
+        "<http://example.com/s> <http://example.com/p> \"foo\"@en <http://example.com/g\\u0020a> ."
+    );
+    assert_eq!(
+        quad_to_nquads_line(&data_factory.quad(s, p, o, Some(data_factory.blank_node("g").into()))),
+        "<http://example.com/s> <http://example.com/p> \"foo\\n\\\"bar\\\"\" _:g ."
+    );
+}
+
+#[test]
+fn test_load_dataset_dir() {
+    let dir = env::temp_dir().join(format!("rudf-load-dir-{}", process::id()));
+    fs::create_dir_all(&dir).unwrap();
+    fs::write(
+        dir.join("a.ttl"),
+        "@prefix ex: <http://example.com/> .\nex:s ex:p \"a\" ; ex:q _:b .\n_:b ex:q ex:s .",
+    )
+    .unwrap();
+    fs::write(
+        dir.join("b c.nt"),
+        "<http://example.com/s> <http://example.com/p> \"b\" .\n\
+         <http://example.com/s> <http://example.com/q> _:b .\n",
+    )
+    .unwrap();
+    fs::write(dir.join("README.txt"), "not RDF").unwrap();
+
+    let data_factory = DataFactory::default();
+    let result = load_dataset_dir(&dir, &data_factory);
+    fs::write(dir.join("invalid.nt"), "<http://example.com/s> .\n").unwrap();
+    let invalid_result = load_dataset_dir(&dir, &data_factory);
+    let dir_iri = format!("file://{}", dir.canonicalize().unwrap().display());
+    fs::remove_dir_all(&dir).unwrap();
+
+    let dataset = result.unwrap();
+    let s = data_factory.named_node("http://example.com/s");
+    let p = data_factory.named_node("http://example.com/p");
+    let graph_names: HashSet<Option<NamedOrBlankNode>> = dataset
+        .iter()
+        .map(|quad| quad.graph_name().clone())
+        .collect();
+    assert_eq!(graph_names.len(), 2);
+    assert_eq!(dataset.len(), 5);
+    assert!(dataset.iter().all(|quad| quad
+        .graph_name()
+        .as_ref()
+        .is_some_and(|g| g.value().starts_with("file:///"))));
+    let graph_a: NamedOrBlankNode = data_factory.named_node(format!("{}/a.ttl", dir_iri)).into();
+    assert!(dataset.contains(&data_factory.quad(
+        s.clone(),
+        p.clone(),
+        data_factory.simple_literal("a"),
+        Some(graph_a.clone())
+    )));
+    let graph_b: NamedOrBlankNode = data_factory
+        .named_node(format!("{}/b%20c.nt", dir_iri))
+        .into();
+    assert!(dataset.contains(&data_factory.quad(
+        s,
+        p,
+        data_factory.simple_literal("b"),
+        Some(graph_b.clone())
+    )));
+    let blank_nodes = |graph_name: &NamedOrBlankNode| {
+        dataset
+            .iter()
+            .filter(|quad| quad.graph_name().as_ref() == Some(graph_name))
+            .flat_map(|quad| vec![Term::from(quad.subject().clone()), quad.object().clone()])
+            .filter(|term| term.kind() == TermKind::BlankNode)
+            .collect::<HashSet<_>>()
+    };
+    let blank_nodes_a = blank_nodes(&graph_a);
+    let blank_nodes_b = blank_nodes(&graph_b);
+    assert_eq!(blank_nodes_a.len(), 1);
+    assert_eq!(blank_nodes_b.len(), 1);
+    assert!(blank_nodes_a.is_disjoint(&blank_nodes_b));
+
+    let error = invalid_result.err().unwrap();
+    assert!(error.path().ends_with("invalid.nt"));
+    match error {
+        LoadError::Syntax { .. } => (),
+        error => panic!("Unexpected error {}", error),
+    }
+}
+
+#[test]
+fn test_subject_to_turtle() {
+    let data_factory = DataFactory::default();
+    let ex = |name: &str| data_factory.named_node(format!("http://example.com/{}", name));
+    let alice: NamedOrBlankNode = ex("alice").into();
+    let triples = vec![
+        data_factory.triple(
+            alice.clone(),
+            ex("name"),
+            data_factory.simple_literal("Alice \"A\""),
+        ),
+        data_factory.triple(
+            alice.clone(),
+            ex("name"),
+            data_factory.language_tagged_literal("Alicia", "es"),
+        ),
+        data_factory.triple(
+            alice.clone(),
+            ex("age"),
+            data_factory.typed_literal(
+                "42",
+                data_factory.named_node("http://www.w3.org/2001/XMLSchema#integer"),
+            ),
+        ),
+        data_factory.triple(
+            alice.clone(),
+            ex("knows"),
+            data_factory.named_node("http://other.com/bob?x=1"),
+        ),
+    ];
+    let mut graph: Graph = triples.iter().cloned().collect();
+    graph.insert(data_factory.triple(ex("bob"), ex("name"), data_factory.simple_literal("Bob")));
+    let mut prefixes = HashMap::new();
+    prefixes.insert("ex".to_owned(), "http://example.com/".to_owned());
+    prefixes.insert(
+        "xsd".to_owned(),
+        "http://www.w3.org/2001/XMLSchema#".to_owned(),
+    );
+    prefixes.insert("unused".to_owned(), "http://unused.com/".to_owned());
+
+    let turtle = turtle::subject_to_turtle(&graph, &alice, &prefixes);
+    assert_eq!(
+        turtle,
+        "@prefix ex: <http://example.com/> .\n\
+         @prefix xsd: <http://www.w3.org/2001/XMLSchema#> .\n\
+         \n\
+         ex:alice ex:age \"42\"^^xsd:integer ;\n    \
+         ex:knows <http://other.com/bob?x=1> ;\n    \
+         ex:name \"Alice \\\"A\\\"\", \"Alicia\"@es .\n"
+    );
+    let parsed: Graph = turtle::read_turtle(turtle.as_bytes(), &data_factory)
+        .unwrap()
+        .collect();
+    assert!(parsed.ground_eq(&triples.into_iter().collect()));
+    assert_eq!(
+        turtle::subject_to_turtle(&graph, &ex("other").into(), &prefixes),
+        ""
+    );
+}
+
+#[test]
+fn test_load_turtle_with_progress() {
+    let data_factory = DataFactory::default();
+    let load = |triple_count: usize, interval: usize| {
+        let turtle = (0..triple_count)
+            .map(|i| {
+                format!(
+                    "<http://example.com/s{}> <http://example.com/p> \"{}\" . # item. {}\n",
+                    i, i, i
+                )
+            })
+            .collect::<String>();
+        let mut graph = Graph::default();
+        let mut reports = Vec::default();
+        turtle::load_turtle_with_progress(
+            &mut graph,
+            turtle.as_bytes(),
+            &data_factory,
+            interval,
+            |count| reports.push(count),
+        )
+        .unwrap();
+        assert_eq!(graph.len(), triple_count);
+        reports
+    };
+    assert_eq!(load(25, 10), vec![10, 20, 25]);
+    // The final count is not reported twice when it is a multiple of the interval
+    assert_eq!(load(20, 10), vec![10, 20]);
+    assert_eq!(load(3, 1), vec![1, 2, 3]);
+    assert_eq!(load(0, 10), vec![0]);
+
+    assert!(turtle::load_turtle_with_progress(
+        &mut Graph::default(),
+        "<http://example.com/s> <".as_bytes(),
+        &data_factory,
+        1,
+        |_| ()
+    )
+    .is_err());
+}
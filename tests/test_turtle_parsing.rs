@@ -2,10 +2,14 @@ extern crate rudf;
 
 use rudf::model;
 use rudf::rio::turtle;
+use rudf::model::data::QuadLike;
+use rudf::model::data::TripleLike;
+use rudf::rio::*;
 use std::fs;
 use std::fs::File;
 use std::io::Read;
 use std::fs::read_dir;
+use std::path::Path;
 
 /// Test all the files in the turtle_test_data directory
 #[test]
@@ -22,3 +26,729 @@ fn test_simple_turtle_parsing() {
         turtle::read_turtle(file_read, &data_factory);
     }
 }
+
+#[test]
+fn test_prefixed_name_datatype() {
+    let data_factory = model::data::DataFactory::default();
+    let triples: Vec<_> = turtle::read_turtle(
+        "@prefix xsd: <http://www.w3.org/2001/XMLSchema#> .\n<http://example.com/s> <http://example.com/p> \"5\"^^xsd:integer .".as_bytes(),
+        &data_factory,
+    )
+    .unwrap()
+    .collect();
+    assert_eq!(
+        triples,
+        vec![data_factory.triple(
+            data_factory.named_node("http://example.com/s"),
+            data_factory.named_node("http://example.com/p"),
+            data_factory.typed_literal(
+                "5",
+                data_factory.named_node("http://www.w3.org/2001/XMLSchema#integer")
+            )
+        )]
+    );
+}
+
+#[test]
+fn test_undeclared_prefix_datatype() {
+    let data_factory = model::data::DataFactory::default();
+    assert!(turtle::read_turtle(
+        "<http://example.com/s> <http://example.com/p> \"5\"^^xsd:integer .".as_bytes(),
+        &data_factory,
+    )
+    .is_err());
+}
+
+#[test]
+fn test_push_parser() {
+    let data_factory = model::data::DataFactory::default();
+    let mut parser = turtle::TurtleParser::new(&data_factory);
+    let first = parser.push_chunk(
+        "@prefix ex: <http://example.com/> .\nex:s ex:p 1 .\nex:s ex:p \"a. b\", ex:o".as_bytes(),
+    );
+    assert_eq!(
+        first,
+        vec![data_factory.triple(
+            data_factory.named_node("http://example.com/s"),
+            data_factory.named_node("http://example.com/p"),
+            data_factory.typed_literal(
+                "1",
+                data_factory.named_node("http://www.w3.org/2001/XMLSchema#integer")
+            )
+        )]
+    );
+    let second = parser.push_chunk(".b .\nex:s ex:p \"é".as_bytes());
+    assert_eq!(
+        second,
+        vec![
+            data_factory.triple(
+                data_factory.named_node("http://example.com/s"),
+                data_factory.named_node("http://example.com/p"),
+                data_factory.simple_literal("a. b")
+            ),
+            data_factory.triple(
+                data_factory.named_node("http://example.com/s"),
+                data_factory.named_node("http://example.com/p"),
+                data_factory.named_node("http://example.com/o.b")
+            )
+        ]
+    );
+    assert_eq!(parser.push_chunk(&[0xC3]), vec![]);
+    assert_eq!(parser.push_chunk(&[0xA9]), vec![]);
+    assert_eq!(parser.push_chunk("\" .".as_bytes()), vec![]);
+    assert_eq!(
+        parser.finish().unwrap(),
+        vec![data_factory.triple(
+            data_factory.named_node("http://example.com/s"),
+            data_factory.named_node("http://example.com/p"),
+            data_factory.simple_literal("éé")
+        )]
+    );
+}
+
+#[test]
+fn test_push_parser_error() {
+    let data_factory = model::data::DataFactory::default();
+    let mut parser = turtle::TurtleParser::new(&data_factory);
+    assert_eq!(
+        parser.push_chunk("<http://example.com/s> <http://example.com/p> . ".as_bytes()),
+        vec![]
+    );
+    assert!(parser.finish().is_err());
+}
+
+#[test]
+fn test_push_parser_comments_and_strings() {
+    let data_factory = model::data::DataFactory::default();
+    let file = "@prefix ex: <http://example.com/a.b/> . # a. comment. with dots\n\
+                ex:s ex:p \"a. b\", '''c. \"\"\"d. ''', \"\"\"e. '''f. \"\"\" . # g.\n\
+                ex:s ex:p [ ex:q ex:r ] . ex:s ex:p ( 1 2.5 ) .\n\
+                <http://example.com/s> <http://example.com/p> <http://example.com/o> . # foo. bar\n";
+    let expected: Vec<_> = turtle::read_turtle(file.as_bytes(), &data_factory)
+        .unwrap()
+        .collect();
+    assert_eq!(expected.len(), 11);
+
+    for chunk_size in &[1, 2, 7, file.len()] {
+        // A new factory to get the same blank node identifiers
+        let mut parser = turtle::TurtleParser::new(&model::data::DataFactory::default());
+        let mut triples = Vec::new();
+        for chunk in file.as_bytes().chunks(*chunk_size) {
+            triples.extend(parser.push_chunk(chunk));
+        }
+        triples.extend(parser.finish().unwrap());
+        assert_eq!(triples, expected, "Chunks of {} bytes", chunk_size);
+    }
+
+    // The error positions are relative to the document start
+    let invalid = format!("{}<http://example.com/s> . # h. i\n", file);
+    let error = |result: RioResult<Vec<model::data::Triple>>| {
+        result
+            .unwrap_err()
+            .downcast_ref::<turtle::TurtleSyntaxError>()
+            .unwrap()
+            .clone()
+    };
+    let expected = error(
+        turtle::read_turtle(invalid.as_bytes(), &data_factory).map(|triples| triples.collect()),
+    );
+    let mut parser = turtle::TurtleParser::new(&data_factory);
+    for chunk in invalid.as_bytes().chunks(3) {
+        parser.push_chunk(chunk);
+    }
+    let actual = error(parser.finish());
+    assert_eq!(actual.kind, expected.kind);
+    assert_eq!((actual.start, actual.end), (expected.start, expected.end));
+    // The expected tokens are listed in any order after the line and column
+    let position = |error: &turtle::TurtleSyntaxError| {
+        error.message.split(": ").next().unwrap().to_owned()
+    };
+    assert_eq!(position(&actual), position(&expected));
+    assert_eq!(expected.start, file.len() + 23);
+}
+
+#[test]
+fn test_lenient_keywords() {
+    let data_factory = model::data::DataFactory::default();
+    let document = "<http://example.com/s> A <http://example.com/C> ; <http://example.com/p> TRUE, False .";
+    let lenient = turtle::TurtleOptions::default().with_lenient(true);
+    let triples: Vec<_> =
+        turtle::read_turtle_with_options(document.as_bytes(), &data_factory, &lenient)
+            .unwrap()
+            .collect();
+    let s = data_factory.named_node("http://example.com/s");
+    let p = data_factory.named_node("http://example.com/p");
+    let boolean = data_factory.named_node("http://www.w3.org/2001/XMLSchema#boolean");
+    assert_eq!(
+        triples,
+        vec![
+            data_factory.triple(
+                s.clone(),
+                data_factory.named_node("http://www.w3.org/1999/02/22-rdf-syntax-ns#type"),
+                data_factory.named_node("http://example.com/C")
+            ),
+            data_factory.triple(
+                s.clone(),
+                p.clone(),
+                data_factory.typed_literal("true", boolean.clone())
+            ),
+            data_factory.triple(s, p, data_factory.typed_literal("false", boolean)),
+        ]
+    );
+
+    assert!(turtle::read_turtle(document.as_bytes(), &data_factory).is_err());
+    assert!(turtle::read_turtle(
+        "<http://example.com/s> <http://example.com/p> TRUE .".as_bytes(),
+        &data_factory
+    )
+    .is_err());
+}
+
+#[test]
+fn test_read_turtle_path() {
+    let data_factory = model::data::DataFactory::default();
+    let triples =
+        turtle::read_turtle_path("tests/turtle_test_data/IRI_subject.ttl", &data_factory).unwrap();
+    assert_eq!(
+        triples,
+        vec![data_factory.triple(
+            data_factory.named_node("http://a.example/s"),
+            data_factory.named_node("http://a.example/p"),
+            data_factory.named_node("http://a.example/o"),
+        )]
+    );
+
+    match turtle::read_turtle_path("tests/turtle_test_data/missing.ttl", &data_factory) {
+        Err(error @ turtle::TurtleError::Io { .. }) => {
+            assert_eq!(error.path(), Path::new("tests/turtle_test_data/missing.ttl"));
+            assert!(error.to_string().contains("missing.ttl"));
+        }
+        result => panic!("Unexpected result: {:?}", result),
+    }
+
+    let bad_file = "tests/turtle_test_data/turtle-syntax-bad-struct-01.ttl";
+    match turtle::read_turtle_path(bad_file, &data_factory) {
+        Err(error @ turtle::TurtleError::Syntax { .. }) => {
+            assert_eq!(error.path(), Path::new(bad_file))
+        }
+        result => panic!("Unexpected result: {:?}", result),
+    }
+}
+
+#[test]
+fn test_escape_literal() {
+    for value in &[
+        "",
+        "simple",
+        "with \"quotes\" and 'apostrophes'",
+        "multi\nline\r\n\ttabbed",
+        "back\\slash",
+        "control \u{08}\u{0C}\u{01}\u{7F}",
+        "unicode é 𝄞",
+    ] {
+        assert_eq!(
+            turtle::unescape_literal(&turtle::escape_literal(value)).as_ref(),
+            Ok(&value.to_string())
+        );
+    }
+    assert_eq!(turtle::escape_literal("a\"b\nc\u{01}"), "a\\\"b\\nc\\u0001");
+    assert_eq!(
+        turtle::unescape_literal("\\u00E9\\U0001D11E\\'"),
+        Ok("é𝄞'".to_owned())
+    );
+    assert_eq!(
+        turtle::unescape_literal("\\x"),
+        Err(turtle::EscapeError::InvalidEscape('x'))
+    );
+    assert_eq!(
+        turtle::unescape_literal("\\u00"),
+        Err(turtle::EscapeError::UnexpectedEnd)
+    );
+    assert_eq!(
+        turtle::unescape_literal("\\UFFFFFFFF"),
+        Err(turtle::EscapeError::InvalidCodePoint("FFFFFFFF".to_owned()))
+    );
+    assert_eq!(
+        turtle::unescape_literal("\\u+123"),
+        Err(turtle::EscapeError::InvalidCodePoint("+123".to_owned()))
+    );
+}
+
+/// A reader returning one byte at a time
+struct OneByteReader<'a>(&'a [u8]);
+
+impl<'a> Read for OneByteReader<'a> {
+    fn read(&mut self, buffer: &mut [u8]) -> std::io::Result<usize> {
+        match (self.0.split_first(), buffer.first_mut()) {
+            (Some((byte, rest)), Some(first)) => {
+                *first = *byte;
+                self.0 = rest;
+                Ok(1)
+            }
+            _ => Ok(0),
+        }
+    }
+}
+
+#[test]
+fn test_read_prefixes() {
+    let file = "# Header\n\
+                @base <http://example.com/> .\n\
+                @prefix ex: <http://example.com/ns#> . # comment\n\
+                PREFIX : <http://example.com/default#>\n\n\
+                ex:s ex:p ex:o .\n\
+                @prefix late: <http://example.com/late#> .";
+    let (base, prefixes) = turtle::read_prefixes(file.as_bytes()).unwrap();
+    assert_eq!(base, Some("http://example.com/".to_owned()));
+    let mut prefixes: Vec<(String, String)> = prefixes.into_iter().collect();
+    prefixes.sort();
+    assert_eq!(
+        prefixes,
+        vec![
+            ("".to_owned(), "http://example.com/default#".to_owned()),
+            ("ex".to_owned(), "http://example.com/ns#".to_owned()),
+        ]
+    );
+
+    // The directives cut between two reads are completed and the reading stops at the first triple
+    let (base, split_prefixes) =
+        turtle::read_prefixes(OneByteReader(file.as_bytes()).chain(FailingReader)).unwrap();
+    assert_eq!(base, Some("http://example.com/".to_owned()));
+    let mut split_prefixes: Vec<(String, String)> = split_prefixes.into_iter().collect();
+    split_prefixes.sort();
+    assert_eq!(split_prefixes, prefixes);
+
+    let (base, prefixes) = turtle::read_prefixes(
+        "<http://a.example/s> <http://a.example/p> <http://a.example/o> .".as_bytes(),
+    )
+    .unwrap();
+    assert_eq!(base, None);
+    assert!(prefixes.is_empty());
+
+    // A prefixed name using a keyword as prefix starts a triple
+    let (_, prefixes) = turtle::read_prefixes(
+        "@prefix prefix: <http://example.com/ns#> .\n\
+         prefix:s prefix:p prefix:o .\n\
+         @prefix late: <http://example.com/late#> ."
+            .as_bytes(),
+    )
+    .unwrap();
+    assert_eq!(prefixes.len(), 1);
+
+    let error = turtle::read_prefixes(
+        "@prefix ex: <http://example.com/> .\n@prefix bad: <http://example.com/a b> .".as_bytes(),
+    )
+    .unwrap_err();
+    assert!(error.to_string().starts_with("error at 2:"), "{}", error);
+    assert!(turtle::read_prefixes("@prefix ex: <http://example.com/>".as_bytes()).is_err());
+}
+
+#[test]
+fn test_syntax_error_span() {
+    let data_factory = model::data::DataFactory::default();
+    let span = |file: &str| {
+        let error = match turtle::read_turtle(file.as_bytes(), &data_factory) {
+            Ok(_) => panic!("{} should be invalid", file),
+            Err(error) => error,
+        };
+        let error = error
+            .downcast_ref::<turtle::TurtleSyntaxError>()
+            .unwrap()
+            .clone();
+        (error.start, error.end)
+    };
+
+    // An IRI with an escaped space
+    let file = "<http://example.com/s> <http://example.com/p> <http://example.com/\\u0020> .";
+    assert_eq!(span(file), (46, 73));
+    assert_eq!(&file[46..73], "<http://example.com/\\u0020>");
+
+    // A literal with an escaped surrogate
+    let file = "<http://example.com/s> <http://example.com/p> \"a\\uD800\" .";
+    assert_eq!(&file[span(file).0..span(file).1], "\\uD800");
+
+    // A generic syntax error only has a position
+    let (start, end) = span("<http://example.com/s> <http://example.com/p> .");
+    assert_eq!(start, end);
+}
+
+#[test]
+fn test_n3_features() {
+    let data_factory = model::data::DataFactory::default();
+    let error = |file: &str| match turtle::read_turtle(file.as_bytes(), &data_factory) {
+        Ok(_) => panic!("{} should be invalid", file),
+        Err(error) => error
+            .downcast_ref::<turtle::TurtleSyntaxError>()
+            .unwrap()
+            .clone(),
+    };
+
+    let file = "<http://example.com/a> => <http://example.com/b> .";
+    let implication = error(file);
+    assert_eq!(
+        implication.kind,
+        turtle::TurtleSyntaxErrorKind::UnsupportedN3Feature
+    );
+    assert_eq!(&file[implication.start..implication.end], "=>");
+
+    let formula =
+        error("{ <http://example.com/a> <http://example.com/p> <http://example.com/b> } => { } .");
+    assert_eq!(
+        formula.kind,
+        turtle::TurtleSyntaxErrorKind::UnsupportedN3Feature
+    );
+    assert_eq!(formula.start, 0);
+
+    assert_eq!(
+        error("<http://example.com/a> <http://example.com/p> .").kind,
+        turtle::TurtleSyntaxErrorKind::InvalidSyntax
+    );
+}
+
+#[test]
+fn test_trig_graph_blocks() {
+    let data_factory = model::data::DataFactory::default();
+    let error = |file: &str| match turtle::read_turtle(file.as_bytes(), &data_factory) {
+        Ok(_) => panic!("{} should be invalid", file),
+        Err(error) => error
+            .downcast_ref::<turtle::TurtleSyntaxError>()
+            .unwrap()
+            .clone(),
+    };
+
+    let file = "@prefix ex: <http://example.com/> .\n\
+                ex:s ex:p ex:o .\n\
+                GRAPH ex:g { ex:s ex:p ex:o . }";
+    let graph = error(file);
+    assert_eq!(
+        graph.kind,
+        turtle::TurtleSyntaxErrorKind::UnsupportedTrigFeature
+    );
+    assert_eq!(&file[graph.start..graph.end], "GRAPH ex:g {");
+    assert!(graph.message.contains("TriG"));
+
+    let file = "<http://example.com/g> { <http://example.com/s> <http://example.com/p> <http://example.com/o> . }";
+    let graph = error(file);
+    assert_eq!(
+        graph.kind,
+        turtle::TurtleSyntaxErrorKind::UnsupportedTrigFeature
+    );
+    assert_eq!(&file[graph.start..graph.end], "<http://example.com/g> {");
+
+    assert_eq!(
+        error("_:g { <http://example.com/s> <http://example.com/p> <http://example.com/o> . }").kind,
+        turtle::TurtleSyntaxErrorKind::UnsupportedTrigFeature
+    );
+}
+
+#[test]
+fn test_graph_blocks() {
+    let data_factory = model::data::DataFactory::default();
+    let file = "@prefix ex: <http://example.com/> .\n\
+                ex:s ex:p ex:o .\n\
+                GRAPH ex:g { ex:s ex:p ex:o1 . ex:s ex:p ex:o2 }\n\
+                ex:s ex:p ex:o3 .";
+    let options = turtle::TurtleOptions::default().with_graph_blocks(true);
+    let quads: Vec<_> =
+        turtle::read_turtle_quads(file.as_bytes(), &data_factory, &options)
+            .unwrap()
+            .collect();
+    let ex = |name: &str| data_factory.named_node(format!("http://example.com/{}", name));
+    let graph_name = Some(ex("g").into());
+    assert_eq!(
+        quads,
+        vec![
+            data_factory.quad(ex("s"), ex("p"), ex("o"), None),
+            data_factory.quad(ex("s"), ex("p"), ex("o1"), graph_name.clone()),
+            data_factory.quad(ex("s"), ex("p"), ex("o2"), graph_name),
+            data_factory.quad(ex("s"), ex("p"), ex("o3"), None),
+        ]
+    );
+
+    // The graph blocks are rejected without the option
+    let error = match turtle::read_turtle_quads(
+        file.as_bytes(),
+        &data_factory,
+        &turtle::TurtleOptions::default(),
+    ) {
+        Ok(_) => panic!("The graph block should be rejected"),
+        Err(error) => error,
+    };
+    assert_eq!(
+        error
+            .downcast_ref::<turtle::TurtleSyntaxError>()
+            .unwrap()
+            .kind,
+        turtle::TurtleSyntaxErrorKind::UnsupportedTrigFeature
+    );
+    assert!(
+        turtle::read_turtle_with_options(file.as_bytes(), &data_factory, &options).is_err()
+    );
+
+    // The blank node graph names are kept, rejected or skolemized
+    let file = "@prefix ex: <http://example.com/> .\n\
+                _:g { ex:s ex:p ex:o }";
+    let read = |blank_node_graph_names| {
+        let options = turtle::TurtleOptions::default()
+            .with_graph_blocks(true)
+            .with_blank_node_graph_names(blank_node_graph_names);
+        turtle::read_turtle_quads(file.as_bytes(), &data_factory, &options)
+            .map(|quads| quads.collect::<Vec<_>>())
+    };
+    let kept = read(turtle::BlankNodeGraphNames::Keep).unwrap();
+    match kept[0].graph_name() {
+        Some(model::data::NamedOrBlankNode::BlankNode(_)) => (),
+        _ => panic!("The graph name should be a blank node"),
+    }
+    let error = match read(turtle::BlankNodeGraphNames::Reject) {
+        Ok(_) => panic!("The blank node graph name should be rejected"),
+        Err(error) => error,
+    };
+    let error = error
+        .downcast_ref::<turtle::TurtleSyntaxError>()
+        .unwrap()
+        .clone();
+    assert_eq!(
+        error.kind,
+        turtle::TurtleSyntaxErrorKind::UnsupportedTrigFeature
+    );
+    assert_eq!(&file[error.start..error.end], "_:g {");
+    let skolemized = read(turtle::BlankNodeGraphNames::Skolemize(
+        "http://example.com/".to_owned(),
+    ))
+    .unwrap();
+    let skolem_iri = match skolemized[0].graph_name() {
+        Some(model::data::NamedOrBlankNode::NamedNode(node)) => node.value().to_owned(),
+        _ => panic!("The graph name should be an IRI"),
+    };
+    assert!(skolem_iri.starts_with("http://example.com/.well-known/genid/"));
+    assert!(skolem_iri.len() > "http://example.com/.well-known/genid/".len());
+    assert_eq!(skolemized[0].object(), kept[0].object());
+}
+
+#[test]
+fn test_limit() {
+    let data_factory = model::data::DataFactory::default();
+    let file = (0..1000)
+        .map(|i| format!("<http://example.com/s> <http://example.com/p> \"{}\" .\n", i))
+        .collect::<String>();
+    let read = |file: &str, limit| {
+        let options = turtle::TurtleOptions::default().with_limit(limit);
+        turtle::read_turtle_with_options(file.as_bytes(), &data_factory, &options)
+            .map(|triples| triples.collect::<Vec<_>>())
+    };
+
+    let triples = read(&file, 10).unwrap();
+    assert_eq!(triples.len(), 10);
+    let all: Vec<_> = turtle::read_turtle(file.as_bytes(), &data_factory)
+        .unwrap()
+        .collect();
+    assert_eq!(triples, &all[..10]);
+    assert_eq!(read(&file, 2000).unwrap(), all);
+    assert!(read(&file, 0).unwrap().is_empty());
+
+    // The invalid end of the document is not read
+    let invalid_end = format!("{}<http://example.com/s> <", file);
+    assert_eq!(read(&invalid_end, 10).unwrap().len(), 10);
+    assert!(read(&invalid_end, 2000).is_err());
+
+    // Both parsing paths give the same triples on comments and strings with dots
+    let commented = "@prefix ex: <http://example.com/a.b/> . # a. b\n\
+                     ex:s ex:p \"c. d\", '''e. f''' . # g. h\n\
+                     ex:s ex:p <http://example.com/i.> . # foo. bar";
+    let all: Vec<_> = turtle::read_turtle(commented.as_bytes(), &data_factory)
+        .unwrap()
+        .collect();
+    assert_eq!(all.len(), 3);
+    assert_eq!(read(commented, 10).unwrap(), all);
+    assert_eq!(read(commented, 2).unwrap(), &all[..2]);
+}
+
+#[test]
+fn test_numeric_literals() {
+    // The integer alternative is tried last: it matches the start of the decimals and doubles,
+    // so `1.5` used to be read as the integer `1` followed by a syntax error
+    let data_factory = model::data::DataFactory::default();
+    let triples: Vec<_> = turtle::read_turtle(
+        "<http://example.com/s> <http://example.com/p> 1, -1.5, .5, 1.5e3, 2, 1.5, 1E3, +1.5E-2 ;
+            <http://example.com/q> ( 2.5 ) ."
+            .as_bytes(),
+        &data_factory,
+    )
+    .unwrap()
+    .collect();
+    let xsd = |name: &str| {
+        data_factory.named_node(format!("http://www.w3.org/2001/XMLSchema#{}", name))
+    };
+    let objects: Vec<_> = triples[..8].iter().map(|triple| triple.object().clone()).collect();
+    assert_eq!(
+        objects,
+        vec![
+            data_factory.typed_literal("1", xsd("integer")).into(),
+            data_factory.typed_literal("-1.5", xsd("decimal")).into(),
+            data_factory.typed_literal(".5", xsd("decimal")).into(),
+            data_factory.typed_literal("1.5e3", xsd("double")).into(),
+            data_factory.typed_literal("2", xsd("integer")).into(),
+            data_factory.typed_literal("1.5", xsd("decimal")).into(),
+            data_factory.typed_literal("1E3", xsd("double")).into(),
+            data_factory.typed_literal("+1.5E-2", xsd("double")).into(),
+        ]
+    );
+    assert!(triples.iter().any(|triple| triple.object()
+        == &data_factory.typed_literal("2.5", xsd("decimal")).into()));
+}
+
+#[test]
+fn test_normalize_literals() {
+    let data_factory = model::data::DataFactory::default();
+    let file = "@prefix xsd: <http://www.w3.org/2001/XMLSchema#> .
+        <http://example.com/s> <http://example.com/p> \"01\"^^xsd:integer, +002.50, \"1\"^^xsd:boolean, \"01\", ( 007 ) .";
+    let read = |normalize_literals| {
+        let options = turtle::TurtleOptions::default().with_normalize_literals(normalize_literals);
+        let mut values: Vec<String> =
+            turtle::read_turtle_with_options(file.as_bytes(), &data_factory, &options)
+                .unwrap()
+                .filter_map(|triple| match triple.object() {
+                    model::data::Term::Literal(literal) => Some(literal.value().to_owned()),
+                    _ => None,
+                })
+                .collect();
+        values.sort();
+        values
+    };
+    assert_eq!(read(false), vec!["+002.50", "007", "01", "01", "1"]);
+    assert_eq!(read(true), vec!["01", "1", "2.5", "7", "true"]);
+}
+
+#[test]
+fn test_base_iri_resolution() {
+    let data_factory = model::data::DataFactory::default();
+    let file = "<a> <http://example.com/p> <http://example.com/a/../b> .
+        @base <http://example.com/dir/file> .
+        @prefix http: <http://example.com/hijacked#> .
+        @prefix rel: <ns#> .
+        <a> <http://example.com/p> <http://example.com/a/../b> .
+        BASE <../other/>
+        rel:a http:p <#b> .";
+    let triples: Vec<_> = turtle::read_turtle(file.as_bytes(), &data_factory)
+        .unwrap()
+        .map(|triple| triple.to_string())
+        .collect();
+    assert_eq!(
+        triples,
+        vec![
+            "<a> <http://example.com/p> <http://example.com/a/../b> .",
+            "<http://example.com/dir/a> <http://example.com/p> <http://example.com/b> .",
+            "<http://example.com/dir/ns#a> <http://example.com/hijacked#p> \
+             <http://example.com/other/#b> .",
+        ]
+    );
+    assert!(turtle::read_turtle("@base <relative> .".as_bytes(), &data_factory).is_err());
+}
+
+#[test]
+fn test_default_namespace_prefix() {
+    let data_factory = model::data::DataFactory::default();
+    let triples: Vec<_> = turtle::read_turtle(
+        "@prefix : <http://example.com/> .\n:s :p : .\n: :p :o .".as_bytes(),
+        &data_factory,
+    )
+    .unwrap()
+    .collect();
+    let ex = |name: &str| data_factory.named_node(format!("http://example.com/{}", name));
+    assert_eq!(
+        triples,
+        vec![
+            data_factory.triple(ex("s"), ex("p"), ex("")),
+            data_factory.triple(ex(""), ex("p"), ex("o")),
+        ]
+    );
+}
+
+/// A reader that always fails
+struct FailingReader;
+
+impl Read for FailingReader {
+    fn read(&mut self, _: &mut [u8]) -> std::io::Result<usize> {
+        Err(std::io::Error::other("Failing reader"))
+    }
+}
+
+#[test]
+fn test_parse_turtle_with() {
+    let data_factory = model::data::DataFactory::default();
+    let file = "<http://example.com/s> <http://example.com/p> \"1\", \"2\" .\n\
+                <http://example.com/s> <http://example.com/p> \"3\" .\n\
+                <http://example.com/s> <http://example.com/p> \"4\" .";
+    let mut count = 0;
+    turtle::parse_turtle_with(file.as_bytes(), &data_factory, |_| -> RioResult<()> {
+        count += 1;
+        Ok(())
+    })
+    .unwrap();
+    assert_eq!(count, 4);
+
+    let mut seen = Vec::new();
+    let result = turtle::parse_turtle_with(file.as_bytes(), &data_factory, |triple| {
+        if triple.object().value() == "3" {
+            return Err(RioError::new("Stop"));
+        }
+        seen.push(triple.object().value().to_owned());
+        Ok(())
+    });
+    assert_eq!(result.unwrap_err().to_string(), "Stop");
+    assert_eq!(seen, vec!["1", "2"]);
+
+    // The dots in comments and strings do not end the statements
+    let file = "# a. b\n<http://example.com/s> <http://example.com/p> \"1. 2\" . # c. d\n\
+                <http://example.com/s> <http://example.com/p> <http://example.com/o.> . # e. f";
+    let mut seen = Vec::new();
+    turtle::parse_turtle_with(file.as_bytes(), &data_factory, |triple| -> RioResult<()> {
+        seen.push(triple.object().value().to_owned());
+        Ok(())
+    })
+    .unwrap();
+    assert_eq!(seen, vec!["1. 2", "http://example.com/o."]);
+
+    // The triples of the complete statements are given before the end of the document
+    let mut seen = Vec::new();
+    let source = (&file.as_bytes()[..file.len() - 10]).chain(FailingReader);
+    let result = turtle::parse_turtle_with(source, &data_factory, |triple| -> RioResult<()> {
+        seen.push(triple.object().value().to_owned());
+        Ok(())
+    });
+    assert!(result.is_err());
+    assert_eq!(seen, vec!["1. 2"]);
+
+    assert!(turtle::parse_turtle_with(
+        "<http://example.com/s> .".as_bytes(),
+        &data_factory,
+        |_| -> RioResult<()> { Ok(()) }
+    )
+    .is_err());
+}
+
+#[test]
+fn test_long_strings() {
+    let data_factory = model::data::DataFactory::default();
+    let values = |file: &str| -> Vec<String> {
+        turtle::read_turtle(file.as_bytes(), &data_factory)
+            .unwrap()
+            .filter_map(|triple| match triple.object() {
+                model::data::Term::Literal(literal) => Some(literal.value().to_owned()),
+                _ => None,
+            })
+            .collect()
+    };
+    let subject = "<http://example.com/s> <http://example.com/p>";
+    assert_eq!(values(&format!("{} \"\"\"\"\"\" .", subject)), vec![""]);
+    assert_eq!(values(&format!("{} '''''' .", subject)), vec![""]);
+    assert_eq!(
+        values(&format!("{} \"\"\"\"a\"\"b\"\"\" .", subject)),
+        vec!["\"a\"\"b"]
+    );
+    assert_eq!(
+        values(&format!("{} '''a'b''c\n''' .", subject)),
+        vec!["a'b''c\n"]
+    );
+}